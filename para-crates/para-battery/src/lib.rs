@@ -1,23 +1,99 @@
 //! Crate for calculating Battery levels as percentages, based on voltage/pct profiles via
-//! [`BatteryDischargeProfile`].
+//! [`BatteryDischargeProfile`] or a lookup table of points via [`BatteryCurve`]. Noisy voltage
+//! readings can be smoothed with a [`VoltageFilter`] first. Profiles for common cells are provided
+//! as presets, such as [`CR2032`] or [`LIPO_1S`]. A [`CoulombCounter`] tracks the charge drawn
+//! instead, and can be blended with the voltage based estimates.
 #![no_std]
 
 use core::ops::Range;
 
+/// Errors when building a [`BatteryDischargeProfile`] with
+/// [`BatteryDischargeProfile::try_new`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileError {
+    /// The high voltage isn't above the low voltage.
+    VoltageRange,
+    /// The high percentage is below the low percentage.
+    PctRange,
+}
+
+impl core::fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::VoltageRange => f.write_str("high voltage must be above the low voltage"),
+            Self::PctRange => f.write_str("high percentage must not be below the low percentage"),
+        }
+    }
+}
+
+impl core::error::Error for ProfileError {}
+
 pub struct BatteryDischargeProfile {
     voltage_range: Range<f32>,
     pct_range: Range<f32>,
+    /// Temperature in °C below which the voltage is compensated.
+    reference_temperature: f32,
+    /// Voltage added per °C below the reference temperature.
+    volts_per_degree: f32,
 }
 
 impl BatteryDischargeProfile {
     /// Creates a new discharge profile. Internally, it stores the voltages high/low and pct high/low
     /// as ranges.
+    ///
+    /// `voltage_high` must be above `voltage_low`, and `pct_high` must not be below `pct_low`,
+    /// otherwise the profile gives nonsensical results. Use [`try_new`](Self::try_new) to have
+    /// this checked.
     #[inline]
     pub const fn new(voltage_high: f32, voltage_low: f32, pct_high: f32, pct_low: f32) -> Self {
         Self {
             voltage_range: voltage_low..voltage_high,
             pct_range: pct_low..pct_high,
+            reference_temperature: 25.0,
+            volts_per_degree: 0.0,
+        }
+    }
+
+    /// Sets the temperature compensation used by
+    /// [`calc_pct_compensated`](Self::calc_pct_compensated). Below `reference_temperature` (in °C),
+    /// `volts_per_degree` is added to the voltage for every degree, as cells deliver a lower
+    /// voltage in the cold without being any emptier. Without this, no compensation is applied.
+    #[inline]
+    pub const fn with_temperature_compensation(
+        mut self,
+        reference_temperature: f32,
+        volts_per_degree: f32,
+    ) -> Self {
+        self.reference_temperature = reference_temperature;
+        self.volts_per_degree = volts_per_degree;
+        self
+    }
+
+    /// Creates a new discharge profile, checking that the ranges are sane.
+    ///
+    /// ```
+    /// use para_battery::{BatteryDischargeProfile, ProfileError};
+    ///
+    /// assert!(BatteryDischargeProfile::try_new(3.0, 2.0, 1.0, 0.0).is_ok());
+    /// assert_eq!(
+    ///     BatteryDischargeProfile::try_new(2.0, 3.0, 1.0, 0.0).err(),
+    ///     Some(ProfileError::VoltageRange),
+    /// );
+    /// ```
+    pub const fn try_new(
+        voltage_high: f32,
+        voltage_low: f32,
+        pct_high: f32,
+        pct_low: f32,
+    ) -> Result<Self, ProfileError> {
+        if voltage_high.is_nan() || voltage_low.is_nan() || voltage_high <= voltage_low {
+            return Err(ProfileError::VoltageRange);
         }
+        if pct_high.is_nan() || pct_low.is_nan() || pct_high < pct_low {
+            return Err(ProfileError::PctRange);
+        }
+
+        Ok(Self::new(voltage_high, voltage_low, pct_high, pct_low))
     }
 
     /// Calculates a battery percentage according to the specified range of the discharge profile.
@@ -43,6 +119,90 @@ impl BatteryDischargeProfile {
         }
     }
 
+    /// Calculates a battery percentage like [`calc_pct`](Self::calc_pct), but always returns a
+    /// percentage within `0.0..=1.0`. Voltages outside of the profile give the percentage at the
+    /// nearest end of the profile, and percentages outside of `0.0..=1.0` (or NaN) from a malformed
+    /// profile are clamped.
+    ///
+    /// ```
+    /// use para_battery::BatteryDischargeProfile;
+    ///
+    /// let level = BatteryDischargeProfile::new(3.0, 2.0, 1.0, 0.0);
+    ///
+    /// assert_eq!(level.calc_pct_clamped(2.5), 0.5);
+    /// assert_eq!(level.calc_pct_clamped(3.5), 1.0);
+    /// ```
+    pub fn calc_pct_clamped(&self, voltage: f32) -> f32 {
+        let pct = if voltage < self.voltage_range.start {
+            self.pct_range.start
+        } else if voltage >= self.voltage_range.end {
+            self.pct_range.end
+        } else {
+            self.calc_pct(voltage).unwrap_or(0.0)
+        };
+
+        clamp_pct(pct)
+    }
+
+    /// Calculates a battery percentage like [`calc_pct`](Self::calc_pct), as a whole percentage
+    /// within `0..=100` rounded to the nearest integer, like the BTHome battery object expects.
+    ///
+    /// ```
+    /// use para_battery::BatteryDischargeProfile;
+    ///
+    /// let level = BatteryDischargeProfile::new(3.0, 2.0, 1.0, 0.0);
+    ///
+    /// assert_eq!(level.calc_pct_u8(2.125), Some(13));
+    /// assert_eq!(level.calc_pct_u8(3.5), None);
+    /// ```
+    pub fn calc_pct_u8(&self, voltage: f32) -> Option<u8> {
+        self.calc_pct(voltage).map(pct_to_u8)
+    }
+
+    /// Calculates a battery percentage like [`calc_pct`](Self::calc_pct), after compensating the
+    /// voltage for the temperature (in °C) as set by
+    /// [`with_temperature_compensation`](Self::with_temperature_compensation).
+    ///
+    /// ```
+    /// use para_battery::BatteryDischargeProfile;
+    ///
+    /// let level =
+    ///     BatteryDischargeProfile::new(3.0, 2.0, 1.0, 0.0).with_temperature_compensation(20.0, 0.01);
+    ///
+    /// // 10 °C below the reference adds 0.1 V
+    /// assert_eq!(level.calc_pct_compensated(2.4, 10.0), Some(0.5));
+    /// assert_eq!(level.calc_pct_compensated(2.5, 25.0), Some(0.5));
+    /// ```
+    pub fn calc_pct_compensated(&self, voltage: f32, temperature: f32) -> Option<f32> {
+        let degrees_below = (self.reference_temperature - temperature).max(0.0);
+
+        self.calc_pct(voltage + degrees_below * self.volts_per_degree)
+    }
+
+    /// Calculates the voltage for a battery percentage according to the specified range of the
+    /// discharge profile, the inverse of [`calc_pct`](Self::calc_pct). If the percentage is outside
+    /// of the discharge profile, this method returns `None`.
+    ///
+    /// ```
+    /// use para_battery::BatteryDischargeProfile;
+    ///
+    /// let level = BatteryDischargeProfile::new(3.0, 2.0, 1.0, 0.0);
+    ///
+    /// assert_eq!(level.calc_voltage(0.5), Some(2.5));
+    /// ```
+    pub fn calc_voltage(&self, pct: f32) -> Option<f32> {
+        if self.pct_range.contains(&pct) {
+            Some(
+                self.voltage_range.start
+                    + (pct - self.pct_range.start)
+                        * ((self.voltage_range.end - self.voltage_range.start)
+                            / (self.pct_range.end - self.pct_range.start)),
+            )
+        } else {
+            None
+        }
+    }
+
     /// Calculates a battery level from a range of discharge profiles. Assumes the first
     /// discharge level is the highest, so the levels go from high to low. Percentages values
     /// are from 1.0 to 0.0.
@@ -74,6 +234,341 @@ impl BatteryDischargeProfile {
             .find_map(|level| level.calc_pct(voltage))
             .unwrap_or(0.0)
     }
+
+    /// Calculates a battery level from a range of discharge profiles like
+    /// [`calc_pct_from_profile_range`](Self::calc_pct_from_profile_range), but always returns a
+    /// percentage within `0.0..=1.0`, even if the profiles are malformed.
+    pub fn calc_pct_clamped_from_profile_range<'a>(
+        voltage: f32,
+        levels: impl Iterator<Item = &'a BatteryDischargeProfile>,
+    ) -> f32 {
+        clamp_pct(Self::calc_pct_from_profile_range(voltage, levels))
+    }
+
+    /// Calculates a battery level from a range of discharge profiles like
+    /// [`calc_pct_from_profile_range`](Self::calc_pct_from_profile_range), as a whole percentage
+    /// within `0..=100` rounded to the nearest integer.
+    pub fn calc_pct_u8_from_profile_range<'a>(
+        voltage: f32,
+        levels: impl Iterator<Item = &'a BatteryDischargeProfile>,
+    ) -> u8 {
+        pct_to_u8(Self::calc_pct_from_profile_range(voltage, levels))
+    }
+
+    /// Calculates the voltage for a battery percentage from a range of discharge profiles, the
+    /// inverse of [`calc_pct_from_profile_range`](Self::calc_pct_from_profile_range). Assumes the
+    /// first discharge level is the highest, so the levels go from high to low. Percentages
+    /// outside of the levels are clamped to the highest or lowest voltage.
+    ///
+    /// ```
+    /// use para_battery::BatteryDischargeProfile;
+    ///
+    /// let levels = [
+    ///     BatteryDischargeProfile::new(3.0, 2.5, 1.0, 0.5),
+    ///     BatteryDischargeProfile::new(2.5, 2.0, 0.5, 0.0),
+    /// ];
+    ///
+    /// assert_eq!(BatteryDischargeProfile::calc_voltage_from_profile_range(0.75, levels.iter()), 2.75);
+    /// ```
+    pub fn calc_voltage_from_profile_range<'a>(
+        pct: f32,
+        levels: impl Iterator<Item = &'a BatteryDischargeProfile>,
+    ) -> f32 {
+        let mut levels = levels.peekable();
+
+        if let Some(level) = levels.peek().filter(|level| pct >= level.pct_range.end) {
+            return level.voltage_range.end;
+        }
+
+        let mut lowest = 0.0;
+
+        for level in levels {
+            if let Some(voltage) = level.calc_voltage(pct) {
+                return voltage;
+            }
+            lowest = level.voltage_range.start;
+        }
+
+        lowest
+    }
+}
+
+/// Approximate discharge profiles of a single 1.5V alkaline AA cell at light loads, for use with
+/// [`BatteryDischargeProfile::calc_pct_from_profile_range`].
+pub const ALKALINE_AA: &[BatteryDischargeProfile] = &[
+    BatteryDischargeProfile::new(1.60, 1.40, 1.00, 0.70),
+    BatteryDischargeProfile::new(1.40, 1.25, 0.70, 0.35),
+    BatteryDischargeProfile::new(1.25, 1.10, 0.35, 0.10),
+    BatteryDischargeProfile::new(1.10, 0.90, 0.10, 0.00),
+];
+
+/// Approximate discharge profiles of a single cell LiPo/Li-ion battery, for use with
+/// [`BatteryDischargeProfile::calc_pct_from_profile_range`].
+pub const LIPO_1S: &[BatteryDischargeProfile] = &[
+    BatteryDischargeProfile::new(4.20, 4.00, 1.00, 0.80),
+    BatteryDischargeProfile::new(4.00, 3.80, 0.80, 0.45),
+    BatteryDischargeProfile::new(3.80, 3.70, 0.45, 0.15),
+    BatteryDischargeProfile::new(3.70, 3.50, 0.15, 0.05),
+    BatteryDischargeProfile::new(3.50, 3.20, 0.05, 0.00),
+];
+
+/// Approximate discharge profiles of a CR2032 coin cell at light loads, for use with
+/// [`BatteryDischargeProfile::calc_pct_from_profile_range`].
+pub const CR2032: &[BatteryDischargeProfile] = &[
+    BatteryDischargeProfile::new(3.00, 2.90, 1.00, 0.42),
+    BatteryDischargeProfile::new(2.90, 2.74, 0.42, 0.18),
+    BatteryDischargeProfile::new(2.74, 2.44, 0.18, 0.06),
+    BatteryDischargeProfile::new(2.44, 2.01, 0.06, 0.00),
+];
+
+/// Approximate discharge profiles of a 3.6V lithium thionyl chloride (Li-SOCl2) cell. The
+/// discharge curve is very flat until the cell is nearly empty, so estimates in the middle are
+/// coarse. For use with [`BatteryDischargeProfile::calc_pct_from_profile_range`].
+pub const LI_SOCL2: &[BatteryDischargeProfile] = &[
+    BatteryDischargeProfile::new(3.67, 3.60, 1.00, 0.60),
+    BatteryDischargeProfile::new(3.60, 3.40, 0.60, 0.15),
+    BatteryDischargeProfile::new(3.40, 3.20, 0.15, 0.05),
+    BatteryDischargeProfile::new(3.20, 2.80, 0.05, 0.00),
+];
+
+/// Clamps a percentage to `0.0..=1.0`, mapping NaN to `0.0`.
+fn clamp_pct(pct: f32) -> f32 {
+    if pct.is_nan() {
+        0.0
+    } else {
+        pct.clamp(0.0, 1.0)
+    }
+}
+
+/// Converts a percentage to a whole percentage within `0..=100`, rounding half up.
+fn pct_to_u8(pct: f32) -> u8 {
+    // The percentage isn't negative after clamping, so truncating after adding 0.5 rounds
+    (clamp_pct(pct) * 100.0 + 0.5) as u8
+}
+
+/// A discharge curve of `N` (voltage, pct) points, interpolated linearly between neighbouring
+/// points. Suited for non-linear curves that would otherwise need many
+/// [`BatteryDischargeProfile`] ranges.
+pub struct BatteryCurve<const N: usize> {
+    points: [(f32, f32); N],
+}
+
+impl<const N: usize> BatteryCurve<N> {
+    /// Creates a new discharge curve. The points are ordered from low to high voltage, and the
+    /// percentages must not decrease with the voltage.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are less than two points, or if the points aren't ordered. In `const`
+    /// contexts, this fails to compile instead.
+    pub const fn new(points: [(f32, f32); N]) -> Self {
+        assert!(N >= 2, "A battery curve needs at least two points");
+
+        let mut i = 1;
+        while i < N {
+            assert!(
+                points[i].0 > points[i - 1].0,
+                "Battery curve voltages must be strictly increasing"
+            );
+            assert!(
+                points[i].1 >= points[i - 1].1,
+                "Battery curve percentages must not decrease"
+            );
+            i += 1;
+        }
+
+        Self { points }
+    }
+
+    /// Calculates a battery percentage by interpolating between the two points around the
+    /// voltage. Voltages outside of the curve are clamped to its end points.
+    ///
+    /// ```
+    /// use para_battery::BatteryCurve;
+    ///
+    /// let curve = BatteryCurve::new([(2.0, 0.0), (2.5, 0.2), (3.0, 1.0)]);
+    ///
+    /// assert_eq!(curve.calc_pct(2.75), 0.6);
+    /// assert_eq!(curve.calc_pct(3.5), 1.0);
+    /// ```
+    pub fn calc_pct(&self, voltage: f32) -> f32 {
+        // Index of the first point above the voltage
+        let upper = self.points.partition_point(|&(v, _)| v <= voltage);
+
+        if upper == 0 {
+            return self.points[0].1;
+        }
+        if upper == N {
+            return self.points[N - 1].1;
+        }
+
+        let (v_low, pct_low) = self.points[upper - 1];
+        let (v_high, pct_high) = self.points[upper];
+
+        pct_low + (voltage - v_low) * ((pct_high - pct_low) / (v_high - v_low))
+    }
+
+    /// Calculates a battery percentage like [`calc_pct`](Self::calc_pct), but always returns a
+    /// percentage within `0.0..=1.0`, even if the points of the curve are outside of it.
+    pub fn calc_pct_clamped(&self, voltage: f32) -> f32 {
+        clamp_pct(self.calc_pct(voltage))
+    }
+
+    /// Calculates a battery percentage like [`calc_pct`](Self::calc_pct), as a whole percentage
+    /// within `0..=100` rounded to the nearest integer.
+    pub fn calc_pct_u8(&self, voltage: f32) -> u8 {
+        pct_to_u8(self.calc_pct(voltage))
+    }
+}
+
+/// An exponential moving average filter for smoothing noisy voltage readings.
+///
+/// Each update computes `alpha * sample + (1 - alpha) * average`, so a higher alpha follows the
+/// samples more closely. The first sample seeds the average, so it doesn't start from zero.
+pub struct VoltageFilter {
+    alpha: f32,
+    average: Option<f32>,
+}
+
+impl VoltageFilter {
+    /// Creates a new filter with the given smoothing factor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alpha` isn't within `0.0 < alpha <= 1.0`. In `const` contexts, this fails to
+    /// compile instead.
+    pub const fn new(alpha: f32) -> Self {
+        assert!(
+            alpha > 0.0 && alpha <= 1.0,
+            "The filter alpha must be within 0.0 < alpha <= 1.0"
+        );
+
+        Self {
+            alpha,
+            average: None,
+        }
+    }
+
+    /// Adds a voltage sample and returns the updated average.
+    ///
+    /// ```
+    /// use para_battery::VoltageFilter;
+    ///
+    /// let mut filter = VoltageFilter::new(0.5);
+    ///
+    /// assert_eq!(filter.update(3.0), 3.0);
+    /// assert_eq!(filter.update(2.0), 2.5);
+    /// ```
+    pub fn update(&mut self, voltage: f32) -> f32 {
+        let average = match self.average {
+            Some(average) => self.alpha * voltage + (1.0 - self.alpha) * average,
+            None => voltage,
+        };
+
+        self.average = Some(average);
+
+        average
+    }
+
+    /// Returns the current average, or `None` if no sample was added yet.
+    pub const fn value(&self) -> Option<f32> {
+        self.average
+    }
+
+    /// Clears the average, so the next sample seeds it again.
+    pub fn reset(&mut self) {
+        self.average = None;
+    }
+}
+
+/// Tracks the charge drawn from a battery by integrating the current over time, for loads like
+/// radio transmissions that the voltage only shows with a lag.
+///
+/// The remaining percentage is relative to the nominal capacity, and can be blended with a
+/// voltage based estimate, which doesn't drift over time like the integrated charge does.
+pub struct CoulombCounter {
+    capacity_mah: f32,
+    used_mah: f32,
+    voltage_weight: f32,
+}
+
+impl CoulombCounter {
+    /// Creates a new counter for a full battery with the given nominal capacity in mAh.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity_mah` isn't above `0.0`. In `const` contexts, this fails to compile
+    /// instead.
+    pub const fn new(capacity_mah: f32) -> Self {
+        assert!(capacity_mah > 0.0, "The capacity must be above 0.0 mAh");
+
+        Self {
+            capacity_mah,
+            used_mah: 0.0,
+            voltage_weight: 0.5,
+        }
+    }
+
+    /// Sets the weight of the voltage based estimate used by [`blend`](Self::blend), with the
+    /// rest given to the counted charge. Defaults to `0.5`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `voltage_weight` isn't within `0.0..=1.0`. In `const` contexts, this fails to
+    /// compile instead.
+    pub const fn with_voltage_weight(mut self, voltage_weight: f32) -> Self {
+        assert!(
+            voltage_weight >= 0.0 && voltage_weight <= 1.0,
+            "The voltage weight must be within 0.0..=1.0"
+        );
+
+        self.voltage_weight = voltage_weight;
+        self
+    }
+
+    /// Adds the charge of a current in A drawn for `dt_s` seconds, and returns the remaining
+    /// percentage. A negative current, while charging, adds charge back up to the capacity. The
+    /// drawn charge is limited to the capacity, so charging an overdrawn battery counts right away.
+    ///
+    /// ```
+    /// use para_battery::CoulombCounter;
+    ///
+    /// let mut counter = CoulombCounter::new(200.0);
+    ///
+    /// // 0.1A for 36s is 1mAh
+    /// assert_eq!(counter.update(0.1, 36.0), 0.995);
+    /// ```
+    pub fn update(&mut self, current_a: f32, dt_s: f32) -> f32 {
+        // 1mAh is 3.6 As
+        self.used_mah = (self.used_mah + current_a * dt_s / 3.6).clamp(0.0, self.capacity_mah);
+
+        self.remaining_pct()
+    }
+
+    /// Returns the charge drawn since the battery was full, in mAh, at most the capacity.
+    pub const fn used_mah(&self) -> f32 {
+        self.used_mah
+    }
+
+    /// Returns the remaining charge as a percentage of the capacity within `0.0..=1.0`.
+    pub fn remaining_pct(&self) -> f32 {
+        clamp_pct(1.0 - self.used_mah / self.capacity_mah)
+    }
+
+    /// Blends a voltage based percentage, such as from
+    /// [`BatteryDischargeProfile::calc_pct_from_profile_range`], with the counted remaining
+    /// percentage by the voltage weight. Returns a percentage within `0.0..=1.0`.
+    pub fn blend(&self, voltage_pct: f32) -> f32 {
+        clamp_pct(
+            self.voltage_weight * voltage_pct + (1.0 - self.voltage_weight) * self.remaining_pct(),
+        )
+    }
+
+    /// Sets the remaining percentage, such as from a voltage based estimate at startup or after
+    /// replacing the battery.
+    pub fn set_remaining_pct(&mut self, pct: f32) {
+        self.used_mah = (1.0 - clamp_pct(pct)) * self.capacity_mah;
+    }
 }
 
 #[cfg(test)]
@@ -89,6 +584,174 @@ mod tests {
         assert_eq!(level.calc_pct(1.5), None);
     }
 
+    #[test]
+    fn checked_profile() {
+        let level = BatteryDischargeProfile::try_new(3.0, 2.0, 1.0, 0.0).unwrap();
+        assert_eq!(level.calc_pct(2.5), Some(0.5));
+
+        // A flat percentage is fine
+        assert!(BatteryDischargeProfile::try_new(3.0, 2.0, 0.5, 0.5).is_ok());
+
+        assert_eq!(
+            BatteryDischargeProfile::try_new(2.0, 3.0, 1.0, 0.0).err(),
+            Some(ProfileError::VoltageRange)
+        );
+        assert_eq!(
+            BatteryDischargeProfile::try_new(2.0, 2.0, 1.0, 0.0).err(),
+            Some(ProfileError::VoltageRange)
+        );
+        assert_eq!(
+            BatteryDischargeProfile::try_new(f32::NAN, 2.0, 1.0, 0.0).err(),
+            Some(ProfileError::VoltageRange)
+        );
+        assert_eq!(
+            BatteryDischargeProfile::try_new(3.0, 2.0, 0.0, 1.0).err(),
+            Some(ProfileError::PctRange)
+        );
+    }
+
+    #[test]
+    fn battery_level_clamped() {
+        let level = BatteryDischargeProfile::new(3.0, 2.0, 1.0, 0.0);
+
+        assert_eq!(level.calc_pct_clamped(2.5), 0.5);
+        assert_eq!(level.calc_pct_clamped(3.0), 1.0);
+        assert_eq!(level.calc_pct_clamped(3.5), 1.0);
+        assert_eq!(level.calc_pct_clamped(1.5), 0.0);
+
+        // A malformed profile with percentages beyond 0..=1
+        let malformed = BatteryDischargeProfile::new(3.0, 2.0, 1.5, -0.5);
+        for voltage in [1.0, 2.0, 2.1, 2.5, 2.9, 3.0, 4.0, f32::NAN] {
+            let pct = malformed.calc_pct_clamped(voltage);
+            assert!((0.0..=1.0).contains(&pct), "{voltage} V gave {pct}");
+        }
+
+        let levels = [
+            BatteryDischargeProfile::new(3.0, 2.5, 1.2, 0.5),
+            BatteryDischargeProfile::new(2.5, 2.0, 0.5, -0.3),
+        ];
+        for voltage in [1.5, 2.1, 2.5, 2.9, 3.5] {
+            let pct = BatteryDischargeProfile::calc_pct_clamped_from_profile_range(
+                voltage,
+                levels.iter(),
+            );
+            assert!((0.0..=1.0).contains(&pct), "{voltage} V gave {pct}");
+        }
+        // -0.14 before clamping
+        assert_eq!(
+            BatteryDischargeProfile::calc_pct_clamped_from_profile_range(2.1, levels.iter()),
+            0.0
+        );
+
+        let curve = BatteryCurve::new([(2.0, -0.1), (3.0, 1.1)]);
+        assert_eq!(curve.calc_pct_clamped(1.5), 0.0);
+        assert_eq!(curve.calc_pct_clamped(3.5), 1.0);
+    }
+
+    #[test]
+    fn presets() {
+        for preset in [ALKALINE_AA, LIPO_1S, CR2032, LI_SOCL2] {
+            for level in preset {
+                assert!(
+                    BatteryDischargeProfile::try_new(
+                        level.voltage_range.end,
+                        level.voltage_range.start,
+                        level.pct_range.end,
+                        level.pct_range.start
+                    )
+                    .is_ok()
+                );
+            }
+
+            let high = preset[0].voltage_range.end;
+            let low = preset[preset.len() - 1].voltage_range.start;
+
+            assert_eq!(
+                BatteryDischargeProfile::calc_pct_from_profile_range(high, preset.iter()),
+                1.0
+            );
+            assert_eq!(
+                BatteryDischargeProfile::calc_pct_from_profile_range(low, preset.iter()),
+                0.0
+            );
+
+            // Sweep down the voltage span, the percentage must never rise
+            let steps = 200;
+            let mut last = 1.0;
+            for step in 0..=steps {
+                let voltage = high - (high - low) * step as f32 / steps as f32;
+                let pct =
+                    BatteryDischargeProfile::calc_pct_from_profile_range(voltage, preset.iter());
+
+                assert!(pct <= last, "{pct} > {last} at {voltage}V");
+                last = pct;
+            }
+            assert_eq!(last, 0.0);
+        }
+    }
+
+    #[test]
+    fn battery_level_u8() {
+        let level = BatteryDischargeProfile::new(3.0, 2.0, 1.0, 0.0);
+
+        // Exactly representable .5 boundaries round up
+        assert_eq!(level.calc_pct_u8(2.125), Some(13));
+        assert_eq!(level.calc_pct_u8(2.375), Some(38));
+        assert_eq!(level.calc_pct_u8(2.625), Some(63));
+
+        // Exactly representable values below .5 round down
+        assert_eq!(level.calc_pct_u8(2.0625), Some(6));
+
+        // Just below 12% isn't truncated down to 11%
+        assert!(level.calc_pct(2.12).unwrap() * 100.0 < 12.0);
+        assert_eq!(level.calc_pct_u8(2.12), Some(12));
+
+        assert_eq!(level.calc_pct_u8(2.0), Some(0));
+        assert_eq!(level.calc_pct_u8(3.5), None);
+
+        let levels = [
+            BatteryDischargeProfile::new(3.0, 2.5, 1.0, 0.5),
+            BatteryDischargeProfile::new(2.5, 2.0, 0.5, 0.0),
+        ];
+        let expect_results: [(f32, u8); 5] =
+            [(3.5, 100), (2.75, 75), (2.125, 13), (2.0, 0), (1.5, 0)];
+        for (voltage, pct) in expect_results {
+            assert_eq!(
+                BatteryDischargeProfile::calc_pct_u8_from_profile_range(voltage, levels.iter()),
+                pct
+            );
+        }
+
+        let curve = BatteryCurve::new([(2.0, 0.0), (3.0, 1.0)]);
+        assert_eq!(curve.calc_pct_u8(2.875), 88);
+        assert_eq!(curve.calc_pct_u8(3.5), 100);
+    }
+
+    #[test]
+    fn battery_level_compensated() {
+        let level = BatteryDischargeProfile::new(3.0, 2.0, 1.0, 0.0);
+        let compensated = BatteryDischargeProfile::new(3.0, 2.0, 1.0, 0.0)
+            .with_temperature_compensation(25.0, 0.005);
+
+        // Without compensation set, the temperature doesn't matter
+        assert_eq!(level.calc_pct_compensated(2.5, 0.0), level.calc_pct(2.5));
+
+        // At 0 °C, 25 degrees below the reference, 0.125 V are added
+        let cold = compensated.calc_pct_compensated(2.25, 0.0).unwrap();
+        let uncompensated = compensated.calc_pct(2.25).unwrap();
+        assert_eq!(uncompensated, 0.25);
+        assert!((cold - 0.375).abs() < 1e-5);
+        assert!(cold > uncompensated);
+
+        // A cold cell that reads below the profile is still in range
+        assert_eq!(compensated.calc_pct(1.95), None);
+        assert!(compensated.calc_pct_compensated(1.95, 0.0).is_some());
+
+        // No compensation at or above the reference temperature
+        assert_eq!(compensated.calc_pct_compensated(2.25, 25.0), Some(0.25));
+        assert_eq!(compensated.calc_pct_compensated(2.25, 40.0), Some(0.25));
+    }
+
     #[test]
     fn battery_level_from_profile_range() {
         let levels = [
@@ -105,4 +768,192 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn voltage_from_one_profile() {
+        let level = BatteryDischargeProfile::new(3.0, 2.0, 1.0, 0.0);
+
+        assert_eq!(level.calc_voltage(0.5), Some(2.5));
+        assert_eq!(level.calc_voltage(1.5), None);
+        assert_eq!(level.calc_voltage(-0.5), None);
+
+        for voltage in [2.0, 2.1, 2.33, 2.5, 2.9] {
+            let pct = level.calc_pct(voltage).unwrap();
+            assert!((level.calc_voltage(pct).unwrap() - voltage).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn voltage_from_profile_range() {
+        let levels = [
+            BatteryDischargeProfile::new(3.00, 2.90, 1.00, 0.42),
+            BatteryDischargeProfile::new(2.90, 2.74, 0.42, 0.18),
+            BatteryDischargeProfile::new(2.74, 2.44, 0.18, 0.06),
+            BatteryDischargeProfile::new(2.44, 2.01, 0.06, 0.00),
+        ];
+
+        for voltage in [2.05, 2.3, 2.5, 2.8, 2.95] {
+            let pct = BatteryDischargeProfile::calc_pct_from_profile_range(voltage, levels.iter());
+            let round_trip =
+                BatteryDischargeProfile::calc_voltage_from_profile_range(pct, levels.iter());
+            assert!((round_trip - voltage).abs() < 1e-5);
+        }
+
+        // Out of range percentages are clamped
+        assert_eq!(
+            BatteryDischargeProfile::calc_voltage_from_profile_range(1.5, levels.iter()),
+            3.00
+        );
+        assert_eq!(
+            BatteryDischargeProfile::calc_voltage_from_profile_range(-0.5, levels.iter()),
+            2.01
+        );
+    }
+
+    #[test]
+    fn battery_level_from_curve() {
+        let curve = BatteryCurve::new([(2.0, 0.0), (2.5, 0.25), (3.0, 1.0)]);
+
+        let expect_results: [(f32, f32); 7] = [
+            (1.5, 0.0),
+            (2.0, 0.0),
+            (2.25, 0.125),
+            (2.5, 0.25),
+            (2.75, 0.625),
+            (3.0, 1.0),
+            (3.5, 1.0),
+        ];
+
+        for (voltage, pct) in expect_results {
+            assert_eq!(curve.calc_pct(voltage), pct);
+        }
+    }
+
+    #[test]
+    fn curve_matches_profile_range() {
+        let levels = [
+            BatteryDischargeProfile::new(3.00, 2.90, 1.00, 0.42),
+            BatteryDischargeProfile::new(2.90, 2.74, 0.42, 0.18),
+            BatteryDischargeProfile::new(2.74, 2.44, 0.18, 0.06),
+            BatteryDischargeProfile::new(2.44, 2.01, 0.06, 0.00),
+        ];
+        let curve = BatteryCurve::new([
+            (2.01, 0.00),
+            (2.44, 0.06),
+            (2.74, 0.18),
+            (2.90, 0.42),
+            (3.00, 1.00),
+        ]);
+
+        for voltage in [1.9, 2.1, 2.5, 2.8, 2.95, 3.1] {
+            let expected =
+                BatteryDischargeProfile::calc_pct_from_profile_range(voltage, levels.iter());
+            assert!((curve.calc_pct(voltage) - expected).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "strictly increasing")]
+    fn unordered_curve() {
+        let _ = BatteryCurve::new([(3.0, 1.0), (2.0, 0.0)]);
+    }
+
+    #[test]
+    fn voltage_filter_seeding() {
+        let mut filter = VoltageFilter::new(0.25);
+
+        assert_eq!(filter.value(), None);
+        assert_eq!(filter.update(2.8), 2.8);
+        assert_eq!(filter.value(), Some(2.8));
+
+        filter.reset();
+        assert_eq!(filter.value(), None);
+        assert_eq!(filter.update(2.5), 2.5);
+    }
+
+    #[test]
+    fn voltage_filter_steady_state() {
+        let mut filter = VoltageFilter::new(0.25);
+
+        assert_eq!(filter.update(3.0), 3.0);
+        assert_eq!(filter.update(2.0), 2.75);
+
+        // Noise around a steady voltage is damped
+        let mut filter = VoltageFilter::new(0.25);
+        filter.update(2.9);
+        for sample in [2.95, 2.85].into_iter().cycle().take(100) {
+            let average = filter.update(sample);
+            assert!((average - 2.9).abs() < 0.02);
+        }
+
+        // A constant voltage is converged to
+        for _ in 0..100 {
+            filter.update(2.5);
+        }
+        assert!((filter.value().unwrap() - 2.5).abs() < 1e-5);
+    }
+
+    #[test]
+    #[should_panic(expected = "alpha")]
+    fn voltage_filter_invalid_alpha() {
+        let _ = VoltageFilter::new(0.0);
+    }
+
+    #[test]
+    fn coulomb_counter_integration() {
+        let mut counter = CoulombCounter::new(220.0);
+
+        assert_eq!(counter.used_mah(), 0.0);
+        assert_eq!(counter.remaining_pct(), 1.0);
+
+        // 2A for 9s is 5mAh
+        counter.update(2.0, 9.0);
+        assert!((counter.used_mah() - 5.0).abs() < 1e-4);
+
+        // 10mA for an hour in 100ms steps is 10mAh
+        for _ in 0..36_000 {
+            counter.update(0.01, 0.1);
+        }
+        assert!((counter.used_mah() - 15.0).abs() < 0.01);
+
+        // 7mA for 100 hours drains the rest, without counting past the capacity
+        let remaining = counter.update(0.007, 100.0 * 3600.0);
+        assert_eq!(counter.used_mah(), 220.0);
+        assert_eq!(remaining, 0.0);
+
+        // Charging an overdrawn battery counts right away
+        let remaining = counter.update(-0.011, 3600.0);
+        assert!((counter.used_mah() - 209.0).abs() < 1e-4);
+        assert!((remaining - 0.05).abs() < 1e-6);
+
+        // Charging doesn't go above the capacity
+        counter.set_remaining_pct(0.5);
+        assert_eq!(counter.used_mah(), 110.0);
+        assert_eq!(counter.update(-1.0, 3600.0), 1.0);
+        assert_eq!(counter.used_mah(), 0.0);
+    }
+
+    #[test]
+    fn coulomb_counter_blend() {
+        let mut counter = CoulombCounter::new(100.0).with_voltage_weight(0.25);
+        counter.set_remaining_pct(0.5);
+
+        assert_eq!(counter.blend(0.5), 0.5);
+        assert_eq!(counter.blend(1.0), 0.625);
+        assert_eq!(counter.blend(0.0), 0.375);
+
+        // Out of range voltage estimates are clamped
+        let counter = CoulombCounter::new(100.0).with_voltage_weight(1.0);
+        assert_eq!(counter.blend(1.5), 1.0);
+        assert_eq!(counter.blend(f32::NAN), 0.0);
+
+        let counter = CoulombCounter::new(100.0).with_voltage_weight(0.0);
+        assert_eq!(counter.blend(0.2), 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity")]
+    fn coulomb_counter_invalid_capacity() {
+        let _ = CoulombCounter::new(0.0);
+    }
 }
@@ -3,7 +3,11 @@
 use heapless::Vec;
 use para_fmt::assert;
 
+#[cfg(feature = "shtc3")]
+mod shtc3;
+
 const BR_EDR_NOT_SUPPORTED: u8 = 4;
+const DEVICE_INFO_ENCRYPTED: u8 = 0x01;
 const LE_GENERAL_DISCOVERABLE: u8 = 2;
 
 const BTHOME_AD_HEADER: [u8; 8] = [
@@ -19,8 +23,17 @@ const BTHOME_AD_HEADER: [u8; 8] = [
 
 pub const BTHOME_UUID16: u16 = 0xFCD2;
 
+/// Index of the length byte of the service data AD structure.
+const SERVICE_DATA_LEN_INDEX: usize = 3;
+/// Index of the device info byte of the service data.
+const DEVICE_INFO_INDEX: usize = 7;
+
 macro_rules! impl_fields {
-    { $(($name:ident, $id:literal, $internal_repr:ty, $external_repr:ty),)+ } => {
+    {
+        numeric { $(($name:ident, $id:literal, $internal_repr:ty, $external_repr:ty),)+ }
+        binary { $(($binary_name:ident, $binary_id:literal),)+ }
+        event { $(($event_name:ident, $event_id:literal, $event_repr:ty),)+ }
+    } => {
         $(
             #[derive(Debug, Clone)]
             #[cfg_attr(feature = "defmt", derive(::defmt::Format))]
@@ -55,14 +68,127 @@ macro_rules! impl_fields {
             }
         )*
 
+        $(
+            #[derive(Debug, Clone)]
+            #[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+            pub struct $binary_name([u8; 2]);
+
+            impl $binary_name {
+                const ID: u8 = $binary_id;
+
+                #[inline]
+                pub fn get(&self) -> bool {
+                    self.0[1] != 0
+                }
+            }
+
+            impl From<$binary_name> for BtHomeEnum {
+                fn from(value: $binary_name) -> Self {
+                    Self::$binary_name(value)
+                }
+            }
+
+            impl From<bool> for $binary_name {
+                #[inline]
+                fn from(value: bool) -> Self {
+                    $binary_name([Self::ID, value as u8])
+                }
+            }
+        )*
+
+        $(
+            #[derive(Debug, Clone)]
+            #[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+            pub struct $event_name([u8; 2]);
+
+            impl $event_name {
+                const ID: u8 = $event_id;
+
+                /// Returns `None` for event values unknown to this crate.
+                #[inline]
+                pub fn get(&self) -> Option<$event_repr> {
+                    <$event_repr>::from_u8(self.0[1])
+                }
+            }
+
+            impl From<$event_name> for BtHomeEnum {
+                fn from(value: $event_name) -> Self {
+                    Self::$event_name(value)
+                }
+            }
+
+            impl From<$event_repr> for $event_name {
+                #[inline]
+                fn from(value: $event_repr) -> Self {
+                    $event_name([Self::ID, value as u8])
+                }
+            }
+        )*
+
         #[derive(Debug, Clone)]
-        #[cfg_attr(feature = "defmt", derive(::defmt::Format))]
         pub enum BtHomeEnum {
             $(
                 $name($name),
             )*
+            $(
+                $binary_name($binary_name),
+            )*
+            $(
+                $event_name($event_name),
+            )*
+            Scaled(ScaledObject),
+        }
+
+        /// Formats the object as its name and decoded value, e.g.
+        /// `Temperature10mK(2255)`.
+        impl core::fmt::Display for BtHomeEnum {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                match self {
+                    $(
+                        Self::$name(repr) => write!(f, "{}({:?})", stringify!($name), repr.get()),
+                    )*
+                    $(
+                        Self::$binary_name(repr) => write!(f, "{}({:?})", stringify!($binary_name), repr.get()),
+                    )*
+                    $(
+                        Self::$event_name(repr) => write!(f, "{}({:?})", stringify!($event_name), repr.get()),
+                    )*
+                    Self::Scaled(repr) => write!(
+                        f,
+                        "Scaled({:#04x}, {})",
+                        repr.bytes[0],
+                        para_fmt::Bytes(&repr.bytes[1..repr.len])
+                    ),
+                }
+            }
+        }
+
+        #[cfg(feature = "defmt")]
+        impl ::defmt::Format for BtHomeEnum {
+            fn format(&self, fmt: ::defmt::Formatter) {
+                match self {
+                    $(
+                        Self::$name(repr) => ::defmt::write!(fmt, "{=str}({})", stringify!($name), repr.get()),
+                    )*
+                    $(
+                        Self::$binary_name(repr) => ::defmt::write!(fmt, "{=str}({})", stringify!($binary_name), repr.get()),
+                    )*
+                    $(
+                        Self::$event_name(repr) => ::defmt::write!(fmt, "{=str}({})", stringify!($event_name), repr.get()),
+                    )*
+                    Self::Scaled(repr) => ::defmt::write!(
+                        fmt,
+                        "Scaled({=u8:#04x}, {})",
+                        repr.bytes[0],
+                        para_fmt::Bytes(&repr.bytes[1..repr.len])
+                    ),
+                }
+            }
         }
 
+        /// Objects compare by their id only, so two readings of the same type are
+        /// equal regardless of their values. This is the ordering used when
+        /// sorting data.
         impl PartialEq for BtHomeEnum {
             fn eq(&self, other: &Self) -> bool {
                 self.id() == other.id()
@@ -89,6 +215,13 @@ macro_rules! impl_fields {
                     $(
                         Self::$name(_) => $id,
                     )*
+                    $(
+                        Self::$binary_name(_) => $binary_id,
+                    )*
+                    $(
+                        Self::$event_name(_) => $event_id,
+                    )*
+                    Self::Scaled(repr) => repr.bytes[0],
                 }
             }
 
@@ -97,96 +230,799 @@ macro_rules! impl_fields {
                     $(
                         Self::$name(repr) => &repr.0,
                     )*
+                    $(
+                        Self::$binary_name(repr) => &repr.0,
+                    )*
+                    $(
+                        Self::$event_name(repr) => &repr.0,
+                    )*
+                    Self::Scaled(repr) => &repr.bytes[..repr.len],
+                }
+            }
+
+            /// Encoded length of the object with the given id, including the id byte.
+            const fn encoded_len(id: u8) -> Option<usize> {
+                match id {
+                    $(
+                        $id => Some(core::mem::size_of::<$internal_repr>()),
+                    )*
+                    $(
+                        $binary_id => Some(2),
+                    )*
+                    $(
+                        $event_id => Some(2),
+                    )*
+                    _ => None,
+                }
+            }
+
+            /// Decode a single object, `bytes` must be exactly its encoded length.
+            fn decode(bytes: &[u8]) -> Option<Self> {
+                match *bytes.first()? {
+                    $(
+                        $id => bytes.try_into().ok().map(|repr| Self::$name($name(repr))),
+                    )*
+                    $(
+                        $binary_id => bytes.try_into().ok().map(|repr| Self::$binary_name($binary_name(repr))),
+                    )*
+                    $(
+                        $event_id => bytes.try_into().ok().map(|repr| Self::$event_name($event_name(repr))),
+                    )*
+                    _ => None,
                 }
             }
         }
     }
 }
 
+/// Button event values (BTHome object id 0x3A).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum ButtonEvent {
+    None = 0x00,
+    Press = 0x01,
+    DoublePress = 0x02,
+    TriplePress = 0x03,
+    LongPress = 0x04,
+    LongDoublePress = 0x05,
+    LongTriplePress = 0x06,
+    HoldPress = 0x80,
+}
+
+impl ButtonEvent {
+    const fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0x00 => Some(Self::None),
+            0x01 => Some(Self::Press),
+            0x02 => Some(Self::DoublePress),
+            0x03 => Some(Self::TriplePress),
+            0x04 => Some(Self::LongPress),
+            0x05 => Some(Self::LongDoublePress),
+            0x06 => Some(Self::LongTriplePress),
+            0x80 => Some(Self::HoldPress),
+            _ => None,
+        }
+    }
+}
+
 impl_fields! {
-    (Battery1Per, 0x01, [u8; 2], u8),
-    (Temperature10mK, 0x02, [u8; 3], i16),
-    (Humidity10mPer, 0x03, [u8; 3], u16),
-    (Illuminance10mLux, 0x05, [u8; 4], u32),
-    (Voltage1mV, 0x0C, [u8; 3], u16),
-    (Moisture10mPer, 0x14, [u8; 3], u16),
-    (Humidity1Per, 0x2E, [u8; 2], u8),
-    (Moisture1Per, 0x2F, [u8; 2], u8),
+    numeric {
+        (PacketId, 0x00, [u8; 2], u8),
+        (Battery1Per, 0x01, [u8; 2], u8),
+        (Temperature10mK, 0x02, [u8; 3], i16),
+        (Humidity10mPer, 0x03, [u8; 3], u16),
+        (Pressure10mHpa, 0x04, [u8; 4], u32),
+        (Illuminance10mLux, 0x05, [u8; 4], u32),
+        // Count objects come in three widths: 0x09 (uint8), 0x3D (uint16)
+        // and 0x3E (uint32)
+        (CountU8, 0x09, [u8; 2], u8),
+        (Energy1Wh, 0x0A, [u8; 4], u32),
+        (Power10mW, 0x0B, [u8; 4], u32),
+        (Voltage1mV, 0x0C, [u8; 3], u16),
+        (Pm25Ugm3, 0x0D, [u8; 3], u16),
+        (Pm10Ugm3, 0x0E, [u8; 3], u16),
+        (Co2Ppm, 0x12, [u8; 3], u16),
+        (Moisture10mPer, 0x14, [u8; 3], u16),
+        (Humidity1Per, 0x2E, [u8; 2], u8),
+        (Moisture1Per, 0x2F, [u8; 2], u8),
+        (CountU16, 0x3D, [u8; 3], u16),
+        (CountU32, 0x3E, [u8; 5], u32),
+        (Distance1mm, 0x40, [u8; 3], u16),
+        (Distance100mm, 0x41, [u8; 3], u16),
+        (Current1mA, 0x43, [u8; 3], u16),
+        (Timestamp, 0x50, [u8; 5], u32),
+    }
+    binary {
+        (GenericBoolean, 0x0F),
+        (Power, 0x10),
+        (Opening, 0x11),
+        (BatteryLow, 0x15),
+        (BatteryCharging, 0x16),
+        (Door, 0x1A),
+        (Motion, 0x21),
+        (Occupancy, 0x23),
+    }
+    event {
+        (Button, 0x3A, ButtonEvent),
+    }
+}
+
+/// An object declared through a [`ScaledField`], with its width erased.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ScaledObject {
+    bytes: [u8; 5],
+    len: usize,
+}
+
+/// A numeric object declared outside of this crate.
+///
+/// The value is divided by the object's factor `FACTOR_NUM / FACTOR_DEN`,
+/// truncated to an integer and encoded little endian with `WIDTH` bytes (1 to
+/// 4). E.g. a temperature with 0.01 °C resolution is
+/// `ScaledField<0x02, 1, 100, 2>`.
+///
+/// The width must match the BTHome spec for the id. Data can't be sorted past
+/// objects with an id unknown to this crate, so
+/// [`BtHomeAd::add_data_sorted`] appends after them.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ScaledField<
+    const ID: u8,
+    const FACTOR_NUM: u32,
+    const FACTOR_DEN: u32,
+    const WIDTH: usize,
+>([u8; 5]);
+
+impl<const ID: u8, const FACTOR_NUM: u32, const FACTOR_DEN: u32, const WIDTH: usize> From<f32>
+    for ScaledField<ID, FACTOR_NUM, FACTOR_DEN, WIDTH>
+{
+    #[inline]
+    fn from(value: f32) -> Self {
+        const {
+            core::assert!(WIDTH >= 1 && WIDTH <= 4, "Width must be 1 to 4 bytes");
+            core::assert!(FACTOR_NUM != 0, "Factor must not be zero");
+        };
+
+        let raw = (value * FACTOR_DEN as f32 / FACTOR_NUM as f32) as i64;
+
+        let mut bytes = [0u8; 5];
+        bytes[0] = ID;
+        bytes[1..=WIDTH].copy_from_slice(&raw.to_le_bytes()[..WIDTH]);
+        Self(bytes)
+    }
+}
+
+impl<const ID: u8, const FACTOR_NUM: u32, const FACTOR_DEN: u32, const WIDTH: usize>
+    From<ScaledField<ID, FACTOR_NUM, FACTOR_DEN, WIDTH>> for BtHomeEnum
+{
+    fn from(value: ScaledField<ID, FACTOR_NUM, FACTOR_DEN, WIDTH>) -> Self {
+        Self::Scaled(ScaledObject {
+            bytes: value.0,
+            len: WIDTH + 1,
+        })
+    }
+}
+
+/// A text object (BTHome object id 0x53), e.g. a firmware version.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Text<'a>(&'a str);
+
+impl<'a> Text<'a> {
+    const ID: u8 = 0x53;
+
+    #[inline]
+    pub fn get(&self) -> &'a str {
+        self.0
+    }
+}
+
+impl<'a> From<&'a str> for Text<'a> {
+    fn from(value: &'a str) -> Self {
+        Self(value)
+    }
+}
+
+/// A raw bytes object (BTHome object id 0x54).
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Raw<'a>(&'a [u8]);
+
+impl<'a> Raw<'a> {
+    const ID: u8 = 0x54;
+
+    #[inline]
+    pub fn get(&self) -> &'a [u8] {
+        self.0
+    }
+}
+
+impl<'a> From<&'a [u8]> for Raw<'a> {
+    fn from(value: &'a [u8]) -> Self {
+        Self(value)
+    }
+}
+
+/// Objects with a length-prefixed payload, encoded as `[id, len, bytes...]`.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BtHomeBytes<'a> {
+    Text(Text<'a>),
+    Raw(Raw<'a>),
+}
+
+impl BtHomeBytes<'_> {
+    pub const fn id(&self) -> u8 {
+        match self {
+            Self::Text(_) => Text::ID,
+            Self::Raw(_) => Raw::ID,
+        }
+    }
+
+    /// The payload without the id and length bytes.
+    pub fn payload(&self) -> &[u8] {
+        match self {
+            Self::Text(text) => text.0.as_bytes(),
+            Self::Raw(raw) => raw.0,
+        }
+    }
 }
 
+impl<'a> From<Text<'a>> for BtHomeBytes<'a> {
+    fn from(value: Text<'a>) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl<'a> From<Raw<'a>> for BtHomeBytes<'a> {
+    fn from(value: Raw<'a>) -> Self {
+        Self::Raw(value)
+    }
+}
+
+/// Formats the object as its name and value, e.g. `Text("v1")` or
+/// `Raw([01, 02])`.
+impl core::fmt::Display for BtHomeBytes<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Text(text) => write!(f, "Text({:?})", text.0),
+            Self::Raw(raw) => write!(f, "Raw({})", para_fmt::Bytes(raw.0)),
+        }
+    }
+}
+
+/// An object decoded by [`BtHomeDecoder`].
 #[derive(Debug, Clone)]
+pub enum BtHomeObject<'a> {
+    /// A fixed size object.
+    Data(BtHomeEnum),
+    /// A length-prefixed object, borrowing its payload from the service data.
+    Bytes(BtHomeBytes<'a>),
+}
+
+impl BtHomeObject<'_> {
+    pub const fn id(&self) -> u8 {
+        match self {
+            Self::Data(data) => data.id(),
+            Self::Bytes(bytes) => bytes.id(),
+        }
+    }
+}
+
+/// Formats the object like [`BtHomeEnum`] or [`BtHomeBytes`].
+impl core::fmt::Display for BtHomeObject<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Data(data) => write!(f, "{data}"),
+            Self::Bytes(bytes) => write!(f, "{bytes}"),
+        }
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for BtHomeObject<'_> {
+    fn format(&self, fmt: defmt::Formatter) {
+        match self {
+            Self::Data(data) => defmt::write!(fmt, "{}", data),
+            Self::Bytes(BtHomeBytes::Text(text)) => defmt::write!(fmt, "Text({=str})", text.0),
+            Self::Bytes(BtHomeBytes::Raw(raw)) => {
+                defmt::write!(fmt, "Raw({})", para_fmt::Bytes(raw.0))
+            }
+        }
+    }
+}
+
+impl From<BtHomeEnum> for BtHomeObject<'_> {
+    fn from(value: BtHomeEnum) -> Self {
+        Self::Data(value)
+    }
+}
+
+impl<'a> From<BtHomeBytes<'a>> for BtHomeObject<'a> {
+    fn from(value: BtHomeBytes<'a>) -> Self {
+        Self::Bytes(value)
+    }
+}
+
+/// Encoded length of the object at the start of `data`, including the id
+/// byte and the length byte of length-prefixed objects.
+fn object_len(data: &[u8]) -> Option<usize> {
+    match *data.first()? {
+        Text::ID | Raw::ID => data.get(1).map(|&len| len as usize + 2),
+        id => BtHomeEnum::encoded_len(id),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BtHomeError {
+    BufferFull,
+    Malformed,
+    /// The ad was already encrypted, so its data can't be changed anymore.
+    Encrypted,
+}
+
+#[derive(Debug, Clone)]
 pub struct BtHomeAd<const N: usize> {
     buffer: Vec<u8, N>,
+    /// Bytes kept free for the local name.
+    reserved: usize,
+    /// End of the service data AD structure, where data is appended.
+    service_data_end: usize,
 }
 
 impl<const N: usize> BtHomeAd<N> {
+    /// Create an advertisement containing just the BTHome header.
+    ///
+    /// A buffer too small for the header fails to build:
+    ///
+    /// ```compile_fail
+    /// let ad = para_bthome::BtHomeAd::<4>::new();
+    /// ```
     pub fn new() -> Self {
-        assert!(N >= BTHOME_AD_HEADER.len(), "Ad buffer is too small");
+        const {
+            core::assert!(N >= BTHOME_AD_HEADER.len(), "Ad buffer is too small");
+        };
 
         let buffer = Vec::from_iter(BTHOME_AD_HEADER);
 
-        Self { buffer }
+        Self {
+            buffer,
+            reserved: 0,
+            service_data_end: BTHOME_AD_HEADER.len(),
+        }
+    }
+
+    /// Create an advertisement keeping room for a local name of `name_len`
+    /// bytes, so data that would crowd out the name is rejected when it is
+    /// added. The room is released by adding the local name.
+    pub fn with_reserved_name(name_len: usize) -> Self {
+        let mut ad = Self::new();
+
+        assert!(
+            ad.buffer.len() + name_len + 2 <= N,
+            "Can't reserve room for the local name!"
+        );
+        ad.reserved = name_len + 2;
+
+        ad
+    }
+
+    /// Create an advertisement from the given values, sorted in ascending
+    /// object id order like [`add_data_sorted`](Self::add_data_sorted).
+    ///
+    /// Returns `BtHomeError::BufferFull` if the values don't fit.
+    pub fn from_values(values: impl IntoIterator<Item = BtHomeEnum>) -> Result<Self, BtHomeError> {
+        let mut ad = Self::new();
+
+        for value in values {
+            ad.try_add_data_sorted(value)?;
+        }
+
+        Ok(ad)
     }
 
+    /// Add data in call order.
+    ///
+    /// Objects with the same id may be added multiple times, e.g. for a probe
+    /// and an ambient temperature. Receivers tell them apart by their order.
     pub fn add_data(&mut self, payload: impl Into<BtHomeEnum>) -> &mut Self {
+        assert!(!self.is_encrypted(), "Ad is already encrypted!");
+
         let payload = payload.into();
-        let encoded = payload.encode();
+        let (len, encoded_len) = (self.buffer.len(), payload.encode().len());
 
         assert!(
-            self.buffer.len() + encoded.len() < N,
+            self.try_add_data(payload).is_ok(),
             "Can't fit data into buffer! {}+{}",
-            self.buffer.len(),
-            encoded.len()
+            len,
+            encoded_len
         );
 
-        self.buffer[3] += encoded.len() as u8;
-        self.buffer.extend_from_slice(encoded).ok();
-
         self
     }
 
-    pub fn add_local_name(&mut self, name: &str) -> &Self {
-        let len = name.len() + 1;
+    pub fn try_add_data(
+        &mut self,
+        payload: impl Into<BtHomeEnum>,
+    ) -> Result<&mut Self, BtHomeError> {
+        if self.is_encrypted() {
+            return Err(BtHomeError::Encrypted);
+        }
+
+        let payload = payload.into();
+        let encoded = payload.encode();
+
+        if self.buffer.len() + encoded.len() + self.reserved > N {
+            return Err(BtHomeError::BufferFull);
+        }
+
+        self.insert_service_data(self.service_data_end, encoded);
+
+        Ok(self)
+    }
+
+    /// Add a length-prefixed object, such as [`Text`] or [`Raw`], in call
+    /// order.
+    pub fn add_bytes<'a>(&mut self, payload: impl Into<BtHomeBytes<'a>>) -> &mut Self {
+        assert!(!self.is_encrypted(), "Ad is already encrypted!");
+
+        let payload = payload.into();
+        let (len, encoded_len) = (self.buffer.len(), payload.payload().len() + 2);
 
         assert!(
-            self.buffer.len() + len < N,
-            "Can't fit local name into buffer!"
+            self.try_add_bytes(payload).is_ok(),
+            "Can't fit data into buffer! {}+{}",
+            len,
+            encoded_len
         );
 
-        self.buffer.extend_from_slice(&[len as u8, 0x09]).ok();
-        self.buffer.extend_from_slice(name.as_bytes()).ok();
-
-        // Reborrow as ref to prevent further mutation after we have
-        // added the local name to the ad.
-        &*self
+        self
     }
 
-    pub fn encode(&self) -> &[u8] {
-        &self.buffer
+    /// Add a length-prefixed object, such as [`Text`] or [`Raw`], in call
+    /// order. The payload is never truncated, so payloads that don't fit are
+    /// rejected as a whole.
+    pub fn try_add_bytes<'a>(
+        &mut self,
+        payload: impl Into<BtHomeBytes<'a>>,
+    ) -> Result<&mut Self, BtHomeError> {
+        if self.is_encrypted() {
+            return Err(BtHomeError::Encrypted);
+        }
+
+        let payload = payload.into();
+        let bytes = payload.payload();
+        let encoded_len = bytes.len() + 2;
+
+        if bytes.len() > u8::MAX as usize || self.buffer.len() + encoded_len + self.reserved > N {
+            return Err(BtHomeError::BufferFull);
+        }
+
+        let end = self.service_data_end;
+        self.insert_service_data(end, &[payload.id(), bytes.len() as u8]);
+        self.insert_service_data(end + 2, bytes);
+
+        Ok(self)
     }
-}
 
-impl Default for BtHomeAd<31> {
-    #[inline]
-    fn default() -> Self {
-        Self::new()
+    /// Add data in ascending object id order, as recommended by the BTHome v2
+    /// spec. Objects with the same id keep the order they were added in.
+    pub fn add_data_sorted(&mut self, payload: impl Into<BtHomeEnum>) -> &mut Self {
+        assert!(!self.is_encrypted(), "Ad is already encrypted!");
+
+        let payload = payload.into();
+        let (len, encoded_len) = (self.buffer.len(), payload.encode().len());
+
+        assert!(
+            self.try_add_data_sorted(payload).is_ok(),
+            "Can't fit data into buffer! {}+{}",
+            len,
+            encoded_len
+        );
+
+        self
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    pub fn try_add_data_sorted(
+        &mut self,
+        payload: impl Into<BtHomeEnum>,
+    ) -> Result<&mut Self, BtHomeError> {
+        if self.is_encrypted() {
+            return Err(BtHomeError::Encrypted);
+        }
 
-    #[test]
-    fn basic_add_name() {
-        let mut home = BtHomeAd::default();
+        let payload = payload.into();
+        let encoded = payload.encode();
 
-        let name = "hello";
+        if self.buffer.len() + encoded.len() + self.reserved > N {
+            return Err(BtHomeError::BufferFull);
+        }
 
-        home.add_local_name(name);
+        let end = self.service_data_end;
+        let mut index = BTHOME_AD_HEADER.len();
 
-        assert_eq!(home.buffer.len(), 15);
+        while index < end && self.buffer[index] <= payload.id() {
+            index += object_len(&self.buffer[index..end]).unwrap_or(end);
+        }
+
+        self.insert_service_data(index.min(end), encoded);
+
+        Ok(self)
+    }
+
+    /// Insert bytes into the service data, updating its length byte from the
+    /// tracked end of the service data.
+    fn insert_service_data(&mut self, index: usize, bytes: &[u8]) {
+        for (offset, &byte) in bytes.iter().enumerate() {
+            self.buffer.insert(index + offset, byte).ok();
+        }
+        self.service_data_end += bytes.len();
+        self.buffer[SERVICE_DATA_LEN_INDEX] =
+            (self.service_data_end - SERVICE_DATA_LEN_INDEX - 1) as u8;
+    }
+
+    /// Whether the data was encrypted, after which it can't be changed.
+    fn is_encrypted(&self) -> bool {
+        self.buffer[DEVICE_INFO_INDEX] & DEVICE_INFO_ENCRYPTED != 0
+    }
+
+    /// Set the packet id used by receivers to deduplicate advertisements,
+    /// placing it before any other data.
+    pub fn set_packet_id(&mut self, id: u8) -> &mut Self {
+        assert!(!self.is_encrypted(), "Ad is already encrypted!");
+
+        let start = BTHOME_AD_HEADER.len();
+        let end = self.service_data_end;
+
+        if start < end && self.buffer[start] == PacketId::ID {
+            self.buffer[start + 1] = id;
+            self
+        } else {
+            self.add_data_sorted(PacketId::from(id))
+        }
+    }
+
+    pub fn add_local_name(&mut self, name: &str) -> &Self {
+        assert!(
+            self.try_add_local_name(name).is_ok(),
+            "Can't fit local name into buffer!"
+        );
+
+        // Reborrow as ref to prevent further mutation after we have
+        // added the local name to the ad.
+        &*self
+    }
+
+    pub fn try_add_local_name(&mut self, name: &str) -> Result<&Self, BtHomeError> {
+        let len = name.len() + 1;
+
+        // The length byte isn't included in the length
+        if self.buffer.len() + len + 1 > N {
+            return Err(BtHomeError::BufferFull);
+        }
+
+        self.buffer.extend_from_slice(&[len as u8, 0x09]).ok();
+        self.buffer.extend_from_slice(name.as_bytes()).ok();
+        self.reserved = 0;
+
+        Ok(&*self)
+    }
+
+    pub fn encode(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Return a view of the encoded ad that formats as hex, for logging.
+    pub fn fmt_hex(&self) -> para_fmt::Bytes<'_> {
+        para_fmt::Bytes(&self.buffer)
+    }
+
+    /// Return the number of bytes that can still be added to the ad,
+    /// excluding the room reserved for the local name. Nothing can be added
+    /// to an encrypted ad.
+    pub fn remaining(&self) -> usize {
+        if self.is_encrypted() {
+            return 0;
+        }
+
+        N.saturating_sub(self.buffer.len() + self.reserved)
+    }
+
+    /// Check whether the payload fits into the remaining space of the ad.
+    pub fn can_fit(&self, payload: &(impl Into<BtHomeEnum> + Clone)) -> bool {
+        payload.clone().into().encode().len() <= self.remaining()
+    }
+
+    /// Encrypt the data of the ad in place with AES-CCM, as specified by
+    /// BTHome v2, and return the encoded ad.
+    ///
+    /// The nonce is built from the `mac` address of the device and the
+    /// `counter`, which must be increased for every encrypted ad. The counter
+    /// and 4-byte MIC are appended to the data, so no more data can be added
+    /// afterwards: adding data panics, and the `try_add_*` functions return
+    /// [`BtHomeError::Encrypted`]. A local name can still be added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ad is already encrypted, or if the 8 bytes of encryption
+    /// data don't fit next to the room reserved for the local name.
+    #[cfg(feature = "encryption")]
+    pub fn encode_encrypted(&mut self, key: &[u8; 16], mac: &[u8; 6], counter: u32) -> &[u8] {
+        use aes::Aes128;
+        use ccm::{
+            Ccm,
+            aead::{AeadInPlace, KeyInit},
+            consts::{U4, U13},
+        };
+
+        assert!(!self.is_encrypted(), "Ad is already encrypted!");
+        assert!(
+            self.buffer.len() + 8 + self.reserved <= N,
+            "Can't fit encryption data into buffer!"
+        );
+
+        self.buffer[DEVICE_INFO_INDEX] |= DEVICE_INFO_ENCRYPTED;
+
+        let counter = counter.to_le_bytes();
+        let mut nonce = [0u8; 13];
+        nonce[..6].copy_from_slice(mac);
+        nonce[6..8].copy_from_slice(&BTHOME_UUID16.to_le_bytes());
+        nonce[8] = self.buffer[DEVICE_INFO_INDEX];
+        nonce[9..].copy_from_slice(&counter);
+
+        let end = self.service_data_end;
+        let cipher = Ccm::<Aes128, U4, U13>::new(key.into());
+        let mic = cipher
+            .encrypt_in_place_detached(
+                &nonce.into(),
+                &[],
+                &mut self.buffer[BTHOME_AD_HEADER.len()..end],
+            )
+            .expect("BTHome payloads are within the CCM length limit");
+
+        self.insert_service_data(end, &counter);
+        self.insert_service_data(end + counter.len(), &mic);
+
+        &self.buffer
+    }
+}
+
+/// Parses the service data of a BTHome v2 advertisement.
+///
+/// The service data starts with the UUID16 (little endian) followed by the
+/// device info byte and the objects. Iterating yields the decoded objects in
+/// order, with length-prefixed objects borrowing their payload. Truncated
+/// data, unknown object ids or text that isn't UTF-8 yield a
+/// [`BtHomeError::Malformed`], after which the iteration ends.
+#[derive(Debug, Clone)]
+pub struct BtHomeDecoder<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> BtHomeDecoder<'a> {
+    /// Validate the UUID16 and device info of the service data.
+    ///
+    /// Encrypted advertisements and other BTHome versions are rejected as
+    /// [`BtHomeError::Malformed`].
+    pub fn new(service_data: &'a [u8]) -> Result<Self, BtHomeError> {
+        let [uuid_lo, uuid_hi, device_info, data @ ..] = service_data else {
+            return Err(BtHomeError::Malformed);
+        };
+
+        if u16::from_le_bytes([*uuid_lo, *uuid_hi]) != BTHOME_UUID16
+            || device_info >> 5 != 2
+            || device_info & DEVICE_INFO_ENCRYPTED != 0
+        {
+            return Err(BtHomeError::Malformed);
+        }
+
+        Ok(Self { data })
+    }
+}
+
+/// Formats the remaining objects, e.g. `[Battery1Per(34), Temperature10mK(2255)]`.
+impl core::fmt::Display for BtHomeDecoder<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("[")?;
+        for (i, object) in self.clone().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            match object {
+                Ok(object) => write!(f, "{object}")?,
+                Err(e) => write!(f, "{e:?}")?,
+            }
+        }
+        f.write_str("]")
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for BtHomeDecoder<'_> {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(fmt, "[");
+        for (i, object) in self.clone().enumerate() {
+            if i > 0 {
+                defmt::write!(fmt, ", ");
+            }
+            match object {
+                Ok(object) => defmt::write!(fmt, "{}", object),
+                Err(e) => defmt::write!(fmt, "{}", e),
+            }
+        }
+        defmt::write!(fmt, "]");
+    }
+}
+
+impl<'a> Iterator for BtHomeDecoder<'a> {
+    type Item = Result<BtHomeObject<'a>, BtHomeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let data = self.data;
+        let id = *data.first()?;
+
+        let decoded = object_len(data).and_then(|len| {
+            let bytes = data.get(..len)?;
+            let object = match id {
+                Text::ID => core::str::from_utf8(&bytes[2..])
+                    .ok()
+                    .map(|text| BtHomeBytes::Text(Text(text)).into()),
+                Raw::ID => Some(BtHomeBytes::Raw(Raw(&bytes[2..])).into()),
+                _ => BtHomeEnum::decode(bytes).map(BtHomeObject::Data),
+            };
+            object.map(|object| (object, len))
+        });
+
+        match decoded {
+            Some((object, len)) => {
+                self.data = &data[len..];
+                Some(Ok(object))
+            }
+            None => {
+                self.data = &[];
+                Some(Err(BtHomeError::Malformed))
+            }
+        }
+    }
+}
+
+impl Default for BtHomeAd<31> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats the encoded ad as hex, see [`BtHomeAd::fmt_hex`].
+impl<const N: usize> core::fmt::Display for BtHomeAd<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.fmt_hex())
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<const N: usize> defmt::Format for BtHomeAd<N> {
+    fn format(&self, fmt: defmt::Formatter) {
+        defmt::write!(fmt, "{}", self.fmt_hex())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basic_add_name() {
+        let mut home = BtHomeAd::default();
+
+        let name = "hello";
+
+        home.add_local_name(name);
+
+        assert_eq!(home.buffer.len(), 15);
 
         assert_eq!(
             home.encode(),
@@ -237,6 +1073,803 @@ mod tests {
         );
     }
 
+    #[test]
+    fn negative_temperature() {
+        // -10.00 °C
+        let temperature = Temperature10mK::from(-1000);
+        assert_eq!(temperature.get(), -1000);
+        assert_eq!(BtHomeEnum::from(temperature).encode(), &[0x02, 0x18, 0xFC]);
+
+        for value in [i16::MIN, -1, 0, 1, i16::MAX] {
+            assert_eq!(Temperature10mK::from(value).get(), value);
+        }
+        assert_eq!(
+            BtHomeEnum::from(Temperature10mK::from(-1)).encode(),
+            &[0x02, 0xFF, 0xFF]
+        );
+        assert_eq!(
+            BtHomeEnum::from(Temperature10mK::from(i16::MIN)).encode(),
+            &[0x02, 0x00, 0x80]
+        );
+    }
+
+    #[test]
+    fn air_quality_encoding() {
+        // Example values from the BTHome v2 spec
+        let pressure = Pressure10mHpa::from(100883);
+        assert_eq!(pressure.get(), 100883);
+        assert_eq!(
+            BtHomeEnum::from(pressure).encode(),
+            &[0x04, 0x13, 0x8A, 0x01]
+        );
+
+        let pm25 = Pm25Ugm3::from(3090);
+        assert_eq!(pm25.get(), 3090);
+        assert_eq!(BtHomeEnum::from(pm25).encode(), &[0x0D, 0x12, 0x0C]);
+
+        let pm10 = Pm10Ugm3::from(7170);
+        assert_eq!(pm10.get(), 7170);
+        assert_eq!(BtHomeEnum::from(pm10).encode(), &[0x0E, 0x02, 0x1C]);
+
+        let co2 = Co2Ppm::from(1250);
+        assert_eq!(co2.get(), 1250);
+        assert_eq!(BtHomeEnum::from(co2).encode(), &[0x12, 0xE2, 0x04]);
+    }
+
+    #[test]
+    fn electrical_encoding() {
+        // Example values from the BTHome v2 spec
+        let voltage = Voltage1mV::from(3074);
+        assert_eq!(voltage.get(), 3074);
+        assert_eq!(BtHomeEnum::from(voltage).encode(), &[0x0C, 0x02, 0x0C]);
+
+        let power = Power10mW::from(6914);
+        assert_eq!(power.get(), 6914);
+        assert_eq!(BtHomeEnum::from(power).encode(), &[0x0B, 0x02, 0x1B, 0x00]);
+
+        let energy = Energy1Wh::from(1346067);
+        assert_eq!(energy.get(), 1346067);
+        assert_eq!(BtHomeEnum::from(energy).encode(), &[0x0A, 0x13, 0x8A, 0x14]);
+
+        let current = Current1mA::from(13390);
+        assert_eq!(current.get(), 13390);
+        assert_eq!(BtHomeEnum::from(current).encode(), &[0x43, 0x4E, 0x34]);
+    }
+
+    #[test]
+    fn distance_and_count_encoding() {
+        // Example values from the BTHome v2 spec
+        let distance = Distance1mm::from(12);
+        assert_eq!(distance.get(), 12);
+        assert_eq!(BtHomeEnum::from(distance).encode(), &[0x40, 0x0C, 0x00]);
+
+        // 7.8 m
+        let distance = Distance100mm::from(78);
+        assert_eq!(distance.get(), 78);
+        assert_eq!(BtHomeEnum::from(distance).encode(), &[0x41, 0x4E, 0x00]);
+
+        let count = CountU8::from(96);
+        assert_eq!(count.get(), 96);
+        assert_eq!(BtHomeEnum::from(count).encode(), &[0x09, 0x60]);
+
+        let count = CountU16::from(24585);
+        assert_eq!(count.get(), 24585);
+        assert_eq!(BtHomeEnum::from(count).encode(), &[0x3D, 0x09, 0x60]);
+
+        let count = CountU32::from(1611213866);
+        assert_eq!(count.get(), 1611213866);
+        assert_eq!(
+            BtHomeEnum::from(count).encode(),
+            &[0x3E, 0x2A, 0x2C, 0x09, 0x60]
+        );
+    }
+
+    #[test]
+    fn timestamp_encoding() {
+        // Example value from the BTHome v2 spec: 2023-05-14 19:41:17 UTC
+        let timestamp = Timestamp::from(1_684_093_277);
+        assert_eq!(timestamp.get(), 1_684_093_277);
+        assert_eq!(
+            BtHomeEnum::from(timestamp).encode(),
+            &[0x50, 0x5D, 0x39, 0x61, 0x64]
+        );
+
+        // Little endian, least significant byte first
+        assert_eq!(
+            BtHomeEnum::from(Timestamp::from(0x0403_0201)).encode(),
+            &[0x50, 0x01, 0x02, 0x03, 0x04]
+        );
+    }
+
+    #[test]
+    fn text_and_raw_encoding() {
+        let mut home = BtHomeAd::default();
+        home.add_bytes(Text::from("v1.2"))
+            .add_bytes(Raw::from(&[0xDE, 0xAD][..]));
+
+        assert_eq!(
+            &home.encode()[BTHOME_AD_HEADER.len()..],
+            &[0x53, 4, b'v', b'1', b'.', b'2', 0x54, 2, 0xDE, 0xAD]
+        );
+        assert_eq!(home.buffer[3], 14);
+
+        // Sorted data is placed around the length-prefixed objects
+        home.add_data_sorted(Battery1Per::from(34))
+            .add_data_sorted(Timestamp::from(0x0403_0201));
+        assert_eq!(
+            &home.encode()[BTHOME_AD_HEADER.len()..],
+            &[
+                0x01, 34, 0x50, 0x01, 0x02, 0x03, 0x04, 0x53, 4, b'v', b'1', b'.', b'2', 0x54, 2,
+                0xDE, 0xAD
+            ]
+        );
+    }
+
+    #[test]
+    fn text_overflow() {
+        let mut home = BtHomeAd::<16>::new();
+        home.try_add_bytes(Text::from("abcdef")).unwrap();
+        assert_eq!(home.encode().len(), 16);
+
+        // Payloads are rejected instead of truncated
+        let mut home = BtHomeAd::<16>::new();
+        assert_eq!(
+            home.try_add_bytes(Text::from("abcdefg")).unwrap_err(),
+            BtHomeError::BufferFull
+        );
+        assert_eq!(
+            home.try_add_bytes(Raw::from(&[0u8; 256][..])).unwrap_err(),
+            BtHomeError::BufferFull
+        );
+        assert_eq!(home.encode(), &BTHOME_AD_HEADER);
+    }
+
+    #[test]
+    fn binary_sensor_encoding() {
+        // Example values from the BTHome v2 spec
+        let battery_low = BatteryLow::from(true);
+        core::assert!(battery_low.get());
+        assert_eq!(BtHomeEnum::from(battery_low).encode(), &[0x15, 0x01]);
+
+        let motion = Motion::from(false);
+        core::assert!(!motion.get());
+        assert_eq!(BtHomeEnum::from(motion).encode(), &[0x21, 0x00]);
+
+        let opening = Opening::from(true);
+        assert_eq!(BtHomeEnum::from(opening).encode(), &[0x11, 0x01]);
+    }
+
+    #[test]
+    fn event_encoding() {
+        // Example values from the BTHome v2 spec
+        let button = Button::from(ButtonEvent::Press);
+        assert_eq!(button.get(), Some(ButtonEvent::Press));
+        assert_eq!(BtHomeEnum::from(button).encode(), &[0x3A, 0x01]);
+
+        let button = Button::from(ButtonEvent::HoldPress);
+        assert_eq!(button.get(), Some(ButtonEvent::HoldPress));
+        assert_eq!(BtHomeEnum::from(button).encode(), &[0x3A, 0x80]);
+
+        // Unknown event values can't be interpreted
+        assert_eq!(Button([0x3A, 0x07]).get(), None);
+
+        let mut home = BtHomeAd::default();
+        home.add_data_sorted(Button::from(ButtonEvent::LongPress))
+            .add_data_sorted(BatteryLow::from(true))
+            .add_data_sorted(Battery1Per::from(5));
+
+        assert_eq!(
+            &home.encode()[BTHOME_AD_HEADER.len()..],
+            &[0x01, 5, 0x15, 0x01, 0x3A, 0x04]
+        );
+    }
+
+    #[test]
+    fn scaled_field_encoding() {
+        // 1 byte: battery in 1 %
+        let battery = ScaledField::<0x01, 1, 1, 1>::from(34.0);
+        assert_eq!(BtHomeEnum::from(battery).encode(), &[0x01, 34]);
+
+        // 2 bytes: temperature in 0.01 °C, signed
+        let temperature = ScaledField::<0x02, 1, 100, 2>::from(22.5);
+        assert_eq!(BtHomeEnum::from(temperature).encode(), &[0x02, 0xCA, 0x08]);
+        let temperature = ScaledField::<0x02, 1, 100, 2>::from(-5.25);
+        assert_eq!(BtHomeEnum::from(temperature).encode(), &[0x02, 0xF3, 0xFD]);
+
+        // 3 bytes: pressure in 0.01 hPa, matches the built-in object
+        let pressure = ScaledField::<0x04, 1, 100, 3>::from(1008.5);
+        assert_eq!(
+            BtHomeEnum::from(pressure).encode(),
+            BtHomeEnum::from(Pressure10mHpa::from(100850)).encode()
+        );
+
+        // 4 bytes: count, truncated
+        let count = ScaledField::<0x3E, 1, 1, 4>::from(70_000.75);
+        assert_eq!(
+            BtHomeEnum::from(count.clone()).encode(),
+            &[0x3E, 0x70, 0x11, 0x01, 0x00]
+        );
+
+        // Scaled objects are sorted by their id
+        let mut home = BtHomeAd::default();
+        home.add_data_sorted(count)
+            .add_data_sorted(Battery1Per::from(34));
+        assert_eq!(
+            &home.encode()[BTHOME_AD_HEADER.len()..],
+            &[0x01, 34, 0x3E, 0x70, 0x11, 0x01, 0x00]
+        );
+    }
+
+    #[test]
+    fn add_data_sorted() {
+        let mut home = BtHomeAd::default();
+
+        let encoded = home
+            .add_data_sorted(Moisture1Per::from(36))
+            .add_data_sorted(Voltage1mV::from(2800))
+            .add_data_sorted(Battery1Per::from(34))
+            .add_data_sorted(Humidity1Per::from(34))
+            .add_data_sorted(Temperature10mK::from(2255))
+            .add_data_sorted(Battery1Per::from(35))
+            .add_local_name("rpara")
+            .encode();
+
+        assert_eq!(
+            encoded,
+            &[
+                0x02,
+                0x01,
+                LE_GENERAL_DISCOVERABLE | BR_EDR_NOT_SUPPORTED,
+                0x12,
+                0x16,
+                0xD2,
+                0xFC,
+                0x40,
+                0x01,
+                34,
+                0x01,
+                35,
+                0x02,
+                207,
+                8,
+                0x0C,
+                0xF0,
+                0x0A,
+                0x2E,
+                34,
+                0x2F,
+                36,
+                6,
+                0x09,
+                b'r',
+                b'p',
+                b'a',
+                b'r',
+                b'a',
+            ]
+        );
+
+        // Object ids in the service data are ascending
+        let mut index = BTHOME_AD_HEADER.len();
+        let mut last = 0;
+        while index < home.buffer[3] as usize + 4 {
+            let id = home.buffer[index];
+            core::assert!(id >= last);
+            last = id;
+            index += BtHomeEnum::encoded_len(id).unwrap();
+        }
+    }
+
+    #[test]
+    fn decode_round_trip() {
+        let mut home = BtHomeAd::default();
+
+        home.set_packet_id(7)
+            .add_data_sorted(Temperature10mK::from(-1234))
+            .add_data_sorted(Humidity10mPer::from(5500))
+            .add_data_sorted(Pressure10mHpa::from(100883))
+            .add_data_sorted(BatteryLow::from(true))
+            .add_data_sorted(Button::from(ButtonEvent::DoublePress))
+            .add_local_name("rpara");
+        let encoded = home.encode();
+
+        // Service data without the length and AD type
+        let service_data = &encoded[5..4 + encoded[3] as usize];
+        let decoded: heapless::Vec<BtHomeEnum, 8> = BtHomeDecoder::new(service_data)
+            .unwrap()
+            .map(|object| match object.unwrap() {
+                BtHomeObject::Data(data) => data,
+                object => panic!("Unexpected object {object}"),
+            })
+            .collect();
+
+        let mut objects = decoded.iter();
+        let BtHomeEnum::PacketId(packet_id) = objects.next().unwrap() else {
+            panic!("Expected packet id");
+        };
+        assert_eq!(packet_id.get(), 7);
+        let BtHomeEnum::Temperature10mK(temperature) = objects.next().unwrap() else {
+            panic!("Expected temperature");
+        };
+        assert_eq!(temperature.get(), -1234);
+        let BtHomeEnum::Humidity10mPer(humidity) = objects.next().unwrap() else {
+            panic!("Expected humidity");
+        };
+        assert_eq!(humidity.get(), 5500);
+        let BtHomeEnum::Pressure10mHpa(pressure) = objects.next().unwrap() else {
+            panic!("Expected pressure");
+        };
+        assert_eq!(pressure.get(), 100883);
+        let BtHomeEnum::BatteryLow(battery_low) = objects.next().unwrap() else {
+            panic!("Expected battery low");
+        };
+        core::assert!(battery_low.get());
+        let BtHomeEnum::Button(button) = objects.next().unwrap() else {
+            panic!("Expected button");
+        };
+        assert_eq!(button.get(), Some(ButtonEvent::DoublePress));
+        core::assert!(objects.next().is_none());
+
+        // The encoding is identical
+        let mut reencoded = BtHomeAd::default();
+        for object in decoded {
+            reencoded.add_data(object);
+        }
+        reencoded.add_local_name("rpara");
+        assert_eq!(reencoded.encode(), home.encode());
+    }
+
+    #[test]
+    fn duplicate_objects() {
+        let mut home = BtHomeAd::default();
+
+        home.add_data_sorted(Temperature10mK::from(2255))
+            .add_data_sorted(Battery1Per::from(34))
+            .add_data_sorted(Temperature10mK::from(-500));
+        let encoded = home.encode();
+
+        let temperatures: heapless::Vec<i16, 2> =
+            BtHomeDecoder::new(&encoded[5..4 + encoded[3] as usize])
+                .unwrap()
+                .filter_map(|object| match object.unwrap() {
+                    BtHomeObject::Data(BtHomeEnum::Temperature10mK(temperature)) => {
+                        Some(temperature.get())
+                    }
+                    _ => None,
+                })
+                .collect();
+
+        // Both temperatures are kept in call order
+        assert_eq!(temperatures.as_slice(), &[2255, -500]);
+    }
+
+    #[test]
+    fn display() {
+        use core::fmt::Write;
+
+        let mut ad = BtHomeAd::<31>::new();
+        ad.add_data(Battery1Per::from(34))
+            .add_data(Temperature10mK::from(2255))
+            .add_data(Button::from(ButtonEvent::Press));
+
+        let mut out = heapless::String::<128>::new();
+        write!(out, "{ad}").unwrap();
+        assert_eq!(
+            out.as_str(),
+            "[02, 01, 06, 0b, 16, d2, fc, 40, 01, 22, 02, cf, 08, 3a, 01]"
+        );
+
+        let encoded = ad.encode();
+        let decoder = BtHomeDecoder::new(&encoded[5..]).unwrap();
+        out.clear();
+        write!(out, "{decoder}").unwrap();
+        assert_eq!(
+            out.as_str(),
+            "[Battery1Per(34), Temperature10mK(2255), Button(Some(Press))]"
+        );
+
+        // Malformed data is shown where decoding stopped
+        let decoder = BtHomeDecoder::new(&[0xD2, 0xFC, 0x40, 0x01, 34, 0xFF]).unwrap();
+        out.clear();
+        write!(out, "{decoder}").unwrap();
+        assert_eq!(out.as_str(), "[Battery1Per(34), Malformed]");
+    }
+
+    #[test]
+    fn decode_malformed() {
+        // Wrong UUID, encrypted and too short service data
+        core::assert!(BtHomeDecoder::new(&[0xD3, 0xFC, 0x40]).is_err());
+        core::assert!(BtHomeDecoder::new(&[0xD2, 0xFC, 0x41]).is_err());
+        core::assert!(BtHomeDecoder::new(&[0xD2, 0xFC]).is_err());
+
+        // Truncated temperature
+        let mut decoder = BtHomeDecoder::new(&[0xD2, 0xFC, 0x40, 0x01, 34, 0x02, 207]).unwrap();
+        core::assert!(matches!(
+            decoder.next(),
+            Some(Ok(BtHomeObject::Data(BtHomeEnum::Battery1Per(_))))
+        ));
+        core::assert!(matches!(decoder.next(), Some(Err(BtHomeError::Malformed))));
+        core::assert!(decoder.next().is_none());
+
+        // Unknown object id
+        let mut decoder = BtHomeDecoder::new(&[0xD2, 0xFC, 0x40, 0xFF, 0x00]).unwrap();
+        core::assert!(matches!(decoder.next(), Some(Err(BtHomeError::Malformed))));
+        core::assert!(decoder.next().is_none());
+
+        // No objects
+        core::assert!(
+            BtHomeDecoder::new(&[0xD2, 0xFC, 0x40])
+                .unwrap()
+                .next()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn decode_bytes() {
+        use core::fmt::Write;
+
+        let mut home = BtHomeAd::default();
+        home.add_data(Battery1Per::from(34))
+            .add_bytes(Text::from("v1"))
+            .add_bytes(Raw::from(&[0xAB, 0xCD][..]))
+            .add_data(Temperature10mK::from(2255));
+        let encoded = home.encode();
+
+        let mut decoder = BtHomeDecoder::new(&encoded[5..]).unwrap();
+        core::assert!(matches!(
+            decoder.next(),
+            Some(Ok(BtHomeObject::Data(BtHomeEnum::Battery1Per(_))))
+        ));
+        let Some(Ok(BtHomeObject::Bytes(BtHomeBytes::Text(text)))) = decoder.next() else {
+            panic!("Expected text");
+        };
+        assert_eq!(text.get(), "v1");
+        let Some(Ok(BtHomeObject::Bytes(BtHomeBytes::Raw(raw)))) = decoder.next() else {
+            panic!("Expected raw bytes");
+        };
+        assert_eq!(raw.get(), &[0xAB, 0xCD]);
+        core::assert!(matches!(
+            decoder.next(),
+            Some(Ok(BtHomeObject::Data(BtHomeEnum::Temperature10mK(_))))
+        ));
+        core::assert!(decoder.next().is_none());
+
+        let mut out = heapless::String::<128>::new();
+        write!(out, "{}", BtHomeDecoder::new(&encoded[5..]).unwrap()).unwrap();
+        assert_eq!(
+            out.as_str(),
+            "[Battery1Per(34), Text(\"v1\"), Raw([ab, cd]), Temperature10mK(2255)]"
+        );
+
+        // Truncated payload and text that isn't UTF-8
+        for data in [
+            &[0xD2, 0xFC, 0x40, 0x53, 3, b'v', b'1'][..],
+            &[0xD2, 0xFC, 0x40, 0x53, 1, 0xFF],
+            &[0xD2, 0xFC, 0x40, 0x54],
+        ] {
+            let mut decoder = BtHomeDecoder::new(data).unwrap();
+            core::assert!(matches!(decoder.next(), Some(Err(BtHomeError::Malformed))));
+            core::assert!(decoder.next().is_none());
+        }
+    }
+
+    #[test]
+    fn from_values() {
+        let values = [
+            Moisture1Per::from(36).into(),
+            Temperature10mK::from(2255).into(),
+            Battery1Per::from(34).into(),
+            Humidity1Per::from(34).into(),
+        ];
+
+        let home = BtHomeAd::<31>::from_values(values).unwrap();
+
+        let mut chained = BtHomeAd::default();
+        chained
+            .add_data_sorted(Moisture1Per::from(36))
+            .add_data_sorted(Temperature10mK::from(2255))
+            .add_data_sorted(Battery1Per::from(34))
+            .add_data_sorted(Humidity1Per::from(34));
+
+        assert_eq!(home.encode(), chained.encode());
+
+        let overflow = [
+            Illuminance10mLux::from(45000).into(),
+            Illuminance10mLux::from(45000).into(),
+        ];
+        assert_eq!(
+            BtHomeAd::<15>::from_values(overflow).unwrap_err(),
+            BtHomeError::BufferFull
+        );
+    }
+
+    #[test]
+    fn try_add_buffer_full() {
+        let mut home = BtHomeAd::<16>::new();
+
+        home.try_add_data(Illuminance10mLux::from(45000))
+            .unwrap()
+            .try_add_data(Temperature10mK::from(2255))
+            .unwrap();
+        let len = home.encode().len();
+
+        assert_eq!(
+            home.try_add_data(Battery1Per::from(34)).unwrap_err(),
+            BtHomeError::BufferFull
+        );
+        assert_eq!(
+            home.try_add_data_sorted(Battery1Per::from(34)).unwrap_err(),
+            BtHomeError::BufferFull
+        );
+        assert_eq!(
+            home.try_add_local_name("rpara").unwrap_err(),
+            BtHomeError::BufferFull
+        );
+
+        // Failed additions leave the ad untouched
+        assert_eq!(home.encode().len(), len);
+        assert_eq!(home.buffer[3], 11);
+    }
+
+    #[test]
+    fn fill_buffer_exactly() {
+        let mut home = BtHomeAd::<12>::new();
+        home.try_add_data(Illuminance10mLux::from(45000)).unwrap();
+        assert_eq!(home.encode().len(), 12);
+
+        let mut home = BtHomeAd::<12>::new();
+        home.try_add_data_sorted(Illuminance10mLux::from(45000))
+            .unwrap();
+        assert_eq!(home.encode().len(), 12);
+
+        let mut home = BtHomeAd::<15>::new();
+        home.try_add_local_name("rpara").unwrap();
+        assert_eq!(home.encode().len(), 15);
+
+        // A full legacy advertisement
+        let mut home = BtHomeAd::default();
+        home.add_data(Temperature10mK::from(2255))
+            .add_data(Humidity10mPer::from(5500))
+            .add_local_name("para-sensor-001");
+        assert_eq!(home.encode().len(), 31);
+    }
+
+    #[test]
+    fn remaining_capacity() {
+        let mut home = BtHomeAd::<14>::new();
+        assert_eq!(home.remaining(), 6);
+
+        home.add_data(Temperature10mK::from(2255));
+        assert_eq!(home.remaining(), 3);
+        core::assert!(home.can_fit(&Humidity10mPer::from(5500)));
+        core::assert!(!home.can_fit(&Illuminance10mLux::from(45000)));
+
+        home.add_data(Humidity10mPer::from(5500));
+        assert_eq!(home.remaining(), 0);
+        core::assert!(!home.can_fit(&Battery1Per::from(34)));
+    }
+
+    #[test]
+    fn reserved_name() {
+        let mut home = BtHomeAd::<20>::with_reserved_name(5);
+        assert_eq!(home.remaining(), 5);
+
+        home.try_add_data(Temperature10mK::from(2255)).unwrap();
+        assert_eq!(
+            home.try_add_data(Illuminance10mLux::from(45000))
+                .unwrap_err(),
+            BtHomeError::BufferFull
+        );
+        assert_eq!(
+            home.try_add_data_sorted(Illuminance10mLux::from(45000))
+                .unwrap_err(),
+            BtHomeError::BufferFull
+        );
+        home.try_add_data(Battery1Per::from(34)).unwrap();
+        assert_eq!(home.remaining(), 0);
+
+        home.try_add_local_name("rpara").unwrap();
+        assert_eq!(home.encode().len(), 20);
+        assert_eq!(home.remaining(), 0);
+    }
+
+    #[test]
+    fn local_name_before_data() {
+        let mut home = BtHomeAd::default();
+        home.add_data(Battery1Per::from(34));
+        home.add_local_name("rpara");
+        home.add_data(Temperature10mK::from(2255));
+        home.add_data_sorted(Humidity1Per::from(50));
+        home.add_bytes(Text::from("v1"));
+        home.set_packet_id(7);
+
+        let encoded = home.encode();
+        assert_eq!(encoded[SERVICE_DATA_LEN_INDEX], 17);
+        assert_eq!(
+            &encoded[BTHOME_AD_HEADER.len()..],
+            &[
+                0x00, 7, 0x01, 34, 0x02, 207, 8, 0x2E, 50, 0x53, 2, b'v', b'1', 6, 0x09, b'r',
+                b'p', b'a', b'r', b'a'
+            ]
+        );
+
+        // The service data still decodes
+        let ids: heapless::Vec<u8, 8> = BtHomeDecoder::new(&encoded[5..4 + encoded[3] as usize])
+            .unwrap()
+            .map(|object| object.unwrap().id())
+            .collect();
+        assert_eq!(ids.as_slice(), &[0x00, 0x01, 0x02, 0x2E, 0x53]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't fit local name into buffer!")]
+    fn add_local_name_overflow() {
+        BtHomeAd::default().add_local_name("a very long local name for an ad");
+    }
+
+    #[test]
+    fn packet_id() {
+        let mut home = BtHomeAd::default();
+
+        home.add_data(Battery1Per::from(34))
+            .set_packet_id(7)
+            .add_data(Temperature10mK::from(2255));
+
+        assert_eq!(
+            home.encode(),
+            &[
+                0x02,
+                0x01,
+                LE_GENERAL_DISCOVERABLE | BR_EDR_NOT_SUPPORTED,
+                0x0B,
+                0x16,
+                0xD2,
+                0xFC,
+                0x40,
+                0x00,
+                7,
+                0x01,
+                34,
+                0x02,
+                207,
+                8,
+            ]
+        );
+
+        // Setting the packet id again replaces the existing one
+        home.set_packet_id(8);
+        assert_eq!(
+            &home.encode()[3..],
+            &[
+                0x0B, 0x16, 0xD2, 0xFC, 0x40, 0x00, 8, 0x01, 34, 0x02, 207, 8
+            ]
+        );
+    }
+
+    /// Test vector from the BTHome v2 encryption example.
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn encode_encrypted() {
+        let key = [
+            0x23, 0x1d, 0x39, 0xc1, 0xd7, 0xcc, 0x1a, 0xb1, 0xae, 0xe2, 0x24, 0xcd, 0x09, 0x6d,
+            0xb9, 0x32,
+        ];
+        let mac = [0x54, 0x48, 0xE6, 0x8F, 0x80, 0xA5];
+
+        let mut home = BtHomeAd::default();
+        home.add_data(Temperature10mK::from(2506))
+            .add_data(Humidity10mPer::from(5055));
+
+        let encoded = home.encode_encrypted(&key, &mac, 0x3322_1100);
+
+        assert_eq!(
+            encoded,
+            &[
+                0x02,
+                0x01,
+                LE_GENERAL_DISCOVERABLE | BR_EDR_NOT_SUPPORTED,
+                0x12,
+                0x16,
+                0xD2,
+                0xFC,
+                0x41,
+                0xa4,
+                0x72,
+                0x66,
+                0xc9,
+                0x5f,
+                0x73,
+                0x00,
+                0x11,
+                0x22,
+                0x33,
+                0x78,
+                0x23,
+                0x72,
+                0x14,
+            ]
+        );
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn no_data_after_encryption() {
+        let mut home = BtHomeAd::default();
+        home.add_data(Temperature10mK::from(2506));
+        let encrypted: heapless::Vec<u8, 31> =
+            Vec::from_slice(home.encode_encrypted(&[0; 16], &[0; 6], 1)).unwrap();
+
+        assert_eq!(
+            home.try_add_data(Battery1Per::from(34)).unwrap_err(),
+            BtHomeError::Encrypted
+        );
+        assert_eq!(
+            home.try_add_data_sorted(Battery1Per::from(34)).unwrap_err(),
+            BtHomeError::Encrypted
+        );
+        assert_eq!(
+            home.try_add_bytes(Text::from("v1")).unwrap_err(),
+            BtHomeError::Encrypted
+        );
+        assert_eq!(home.encode(), encrypted.as_slice());
+
+        // The local name isn't part of the encrypted data
+        home.add_local_name("rpara");
+        assert_eq!(&home.encode()[..encrypted.len()], encrypted.as_slice());
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn reserved_name_encrypted() {
+        let mut home = BtHomeAd::<31>::with_reserved_name(5);
+        home.add_data(Temperature10mK::from(2506))
+            .add_data(Humidity10mPer::from(5055));
+        assert_eq!(home.remaining(), 10);
+
+        home.encode_encrypted(&[0; 16], &[0; 6], 1);
+        assert_eq!(home.remaining(), 0);
+        core::assert!(!home.can_fit(&Battery1Per::from(34)));
+
+        home.try_add_local_name("rpara").unwrap();
+        assert_eq!(home.encode().len(), 29);
+    }
+
+    /// Test that encryption doesn't use the room reserved for the local name.
+    #[cfg(feature = "encryption")]
+    #[test]
+    #[should_panic(expected = "Can't fit encryption data into buffer!")]
+    fn reserved_name_encryption_overflow() {
+        let mut home = BtHomeAd::<31>::with_reserved_name(5);
+        home.add_data(Temperature10mK::from(2506))
+            .add_data(Humidity10mPer::from(5055))
+            .add_data(Illuminance10mLux::from(45000))
+            .add_data(Moisture10mPer::from(3632));
+        assert_eq!(home.encode().len(), 21);
+        assert_eq!(home.remaining(), 3);
+
+        home.encode_encrypted(&[0; 16], &[0; 6], 1);
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    #[should_panic(expected = "Ad is already encrypted!")]
+    fn add_data_after_encryption() {
+        let mut home = BtHomeAd::default();
+        home.encode_encrypted(&[0; 16], &[0; 6], 1);
+        home.add_data(Battery1Per::from(34));
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    #[should_panic(expected = "Ad is already encrypted!")]
+    fn set_packet_id_after_encryption() {
+        let mut home = BtHomeAd::default();
+        home.set_packet_id(1);
+        home.encode_encrypted(&[0; 16], &[0; 6], 1);
+        home.set_packet_id(2);
+    }
+
     #[test]
     fn full_payload() {
         let mut home = BtHomeAd::default();
@@ -0,0 +1,62 @@
+//! Conversions from [`para_shtc3`] measurements into BTHome objects.
+
+use para_shtc3::{Humidity, Temperature};
+
+use crate::{Humidity1Per, Humidity10mPer, Temperature10mK};
+
+/// Rounded half away from zero to 0.01 °C, see
+/// [`Temperature::as_10mk_celsius`].
+impl From<Temperature> for Temperature10mK {
+    fn from(temperature: Temperature) -> Self {
+        temperature.as_10mk_celsius().into()
+    }
+}
+
+/// Rounded half away from zero to 0.01 %RH and clamped to 0-100 %RH, see
+/// [`Humidity::as_10mk_percent`].
+impl From<Humidity> for Humidity10mPer {
+    fn from(humidity: Humidity) -> Self {
+        humidity.as_10mk_percent().into()
+    }
+}
+
+/// Truncated to 1 %RH and clamped to 0-100 %RH, see
+/// [`Humidity::as_1k_percent`].
+impl From<Humidity> for Humidity1Per {
+    fn from(humidity: Humidity) -> Self {
+        humidity.as_1k_percent().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn temperature() {
+        let temperature = Temperature::from_millidegrees_celsius(23_735);
+        assert_eq!(Temperature10mK::from(temperature).get(), 2374);
+
+        let temperature = Temperature::from_millidegrees_celsius(-12_345);
+        assert_eq!(Temperature10mK::from(temperature).get(), -1235);
+
+        let temperature = Temperature::from_millidegrees_celsius(-4);
+        assert_eq!(Temperature10mK::from(temperature).get(), 0);
+    }
+
+    #[test]
+    fn humidity() {
+        let humidity = Humidity::from_millipercent(62_968);
+        assert_eq!(Humidity10mPer::from(humidity).get(), 6297);
+        assert_eq!(Humidity1Per::from(humidity).get(), 62);
+
+        // Values above 100 %RH are clamped
+        let humidity = Humidity::from_percent(120.0);
+        assert_eq!(Humidity10mPer::from(humidity).get(), 10_000);
+        assert_eq!(Humidity1Per::from(humidity).get(), 100);
+
+        let humidity = Humidity::from_percent(-3.0);
+        assert_eq!(Humidity10mPer::from(humidity).get(), 0);
+        assert_eq!(Humidity1Per::from(humidity).get(), 0);
+    }
+}
@@ -231,7 +231,23 @@ impl<T, E> Try for Result<T, E> {
     }
 }
 
-pub(crate) struct Bytes<'a>(pub &'a [u8]);
+/// Formats a byte slice as hex, e.g. `[02, 01, 06]`, with both `defmt` and
+/// `core::fmt`.
+#[derive(Debug, Clone, Copy)]
+pub struct Bytes<'a>(pub &'a [u8]);
+
+impl core::fmt::Display for Bytes<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("[")?;
+        for (i, byte) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{byte:02x}")?;
+        }
+        f.write_str("]")
+    }
+}
 
 #[cfg(feature = "defmt")]
 impl defmt::Format for Bytes<'_> {
@@ -0,0 +1,131 @@
+//! Conversion between raw sensor values and fixed point units.
+//!
+//! The datasheet formulas (section 5.11) scale the raw 16-bit value by
+//! `2^-16`. To avoid floating point math, the scale factors are reduced to a
+//! fraction with a power of two denominator:
+//!
+//! - Temperature: `175000 / 2^16 = 21875 / 2^13` milli-degrees celsius
+//! - Humidity: `100000 / 2^16 = 12500 / 2^13` millipercent
+//!
+//! The conversion is then a multiplication followed by a 13-bit shift, which
+//! cannot overflow a `u32` for any raw value.
+
+/// Convert a raw temperature measurement to milli-degrees celsius.
+///
+/// Formula (datasheet 5.11): -45 + 175 * (val / 2^16),
+/// optimized for fixed point math.
+#[inline]
+pub const fn temperature_from_raw(temp_raw: u16) -> i32 {
+    (((temp_raw as u32) * 21875) >> 13) as i32 - 45000
+}
+
+/// Convert a raw humidity measurement to relative humidity in 1/1000 %RH.
+///
+/// Formula (datasheet 5.11): 100 * (val / 2^16),
+/// optimized for fixed point math.
+#[inline]
+pub const fn humidity_from_raw(humi_raw: u16) -> i32 {
+    (((humi_raw as u32) * 12500) >> 13) as i32
+}
+
+/// Convert milli-degrees celsius to the nearest raw temperature value.
+///
+/// Values outside of the sensor's range (-45 to 130 °C) are saturated. For
+/// every raw value, `raw_from_millidegrees(temperature_from_raw(raw))` is
+/// within 1 LSB of `raw`.
+#[inline]
+pub const fn raw_from_millidegrees(millidegrees: i32) -> u16 {
+    let scaled = ((millidegrees as i64 + 45000) << 13) + 21875 / 2;
+    saturate_raw(scaled / 21875)
+}
+
+/// Convert 1/1000 %RH to the nearest raw humidity value.
+///
+/// Values outside of the sensor's range (0 to 100 %RH) are saturated. For
+/// every raw value, `raw_from_millipercent(humidity_from_raw(raw))` is
+/// within 1 LSB of `raw`.
+#[inline]
+pub const fn raw_from_millipercent(millipercent: i32) -> u16 {
+    let scaled = ((millipercent as i64) << 13) + 12500 / 2;
+    saturate_raw(scaled / 12500)
+}
+
+/// Saturate a value to the range of a raw measurement.
+#[inline]
+const fn saturate_raw(value: i64) -> u16 {
+    if value < 0 {
+        0
+    } else if value > u16::MAX as i64 {
+        u16::MAX
+    } else {
+        value as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test conversion of raw measurement results into °C.
+    #[test]
+    fn test_convert_temperature() {
+        let test_data = [
+            (0x0000, -45000),
+            // Datasheet setion 5.11 "Conversion of Sensor Output"
+            ((0b0110_0100_u16 << 8) | 0b1000_1011, 23730),
+        ];
+        for td in &test_data {
+            assert_eq!(temperature_from_raw(td.0), td.1);
+        }
+    }
+
+    /// Test conversion of raw measurement results into %RH.
+    #[test]
+    fn test_convert_humidity() {
+        let test_data = [
+            (0x0000, 0),
+            // Datasheet setion 5.11 "Conversion of Sensor Output"
+            ((0b1010_0001_u16 << 8) | 0b0011_0011, 62968),
+        ];
+        for td in &test_data {
+            assert_eq!(humidity_from_raw(td.0), td.1);
+        }
+    }
+
+    /// Test conversion of raw measurement results into °C and %RH.
+    #[test]
+    fn measurement_conversion() {
+        // Datasheet setion 5.11 "Conversion of Sensor Output"
+        let temperature = temperature_from_raw((0b0110_0100_u16 << 8) | 0b1000_1011);
+        let humidity = humidity_from_raw((0b1010_0001_u16 << 8) | 0b0011_0011);
+        assert_eq!(temperature, 23730);
+        assert_eq!(humidity, 62968);
+    }
+
+    /// Test that every raw temperature survives a round trip within 1 LSB.
+    #[test]
+    fn temperature_round_trip() {
+        for raw in 0..=u16::MAX {
+            let round_trip = raw_from_millidegrees(temperature_from_raw(raw));
+            assert!(round_trip.abs_diff(raw) <= 1, "{raw} -> {round_trip}");
+        }
+    }
+
+    /// Test that every raw humidity survives a round trip within 1 LSB.
+    #[test]
+    fn humidity_round_trip() {
+        for raw in 0..=u16::MAX {
+            let round_trip = raw_from_millipercent(humidity_from_raw(raw));
+            assert!(round_trip.abs_diff(raw) <= 1, "{raw} -> {round_trip}");
+        }
+    }
+
+    /// Test that values outside of the sensor's range are saturated.
+    #[test]
+    fn inverse_saturation() {
+        assert_eq!(raw_from_millidegrees(-50000), 0);
+        assert_eq!(raw_from_millidegrees(140000), u16::MAX);
+        assert_eq!(raw_from_millipercent(-1000), 0);
+        assert_eq!(raw_from_millipercent(101000), u16::MAX);
+    }
+}
@@ -1,16 +1,102 @@
+const CRC8_POLYNOMIAL: u8 = 0x31;
+const CRC8_INIT: u8 = 0xFF;
+
+/// Parameters of the CRC8 checksum used by the sensor.
+///
+/// The defaults match the Sensirion datasheet (polynomial `0x31`, init
+/// `0xFF`), and only need to be changed for clones using a different
+/// checksum.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Crc8Params {
+    /// The generator polynomial, without the implicit x^8 term.
+    pub poly: u8,
+    /// The initial value of the checksum.
+    pub init: u8,
+}
+
+impl Crc8Params {
+    /// The CRC8 parameters from the Sensirion datasheet.
+    pub const SENSIRION: Self = Self {
+        poly: CRC8_POLYNOMIAL,
+        init: CRC8_INIT,
+    };
+
+    /// Calculate the CRC8 checksum with these parameters.
+    #[inline]
+    pub const fn checksum(&self, data: &[u8]) -> u8 {
+        if self.poly == CRC8_POLYNOMIAL && self.init == CRC8_INIT {
+            crc8(data)
+        } else {
+            crc8_bitwise_with(self.poly, self.init, data)
+        }
+    }
+}
+
+impl Default for Crc8Params {
+    fn default() -> Self {
+        Self::SENSIRION
+    }
+}
+
 /// Calculate the CRC8 checksum.
 ///
-/// Implementation based on the reference implementation by Sensirion.
+/// Uses a lookup table when the `crc-table` feature is enabled, and the
+/// bitwise implementation otherwise.
 #[inline]
 pub(crate) const fn crc8(data: &[u8]) -> u8 {
-    const CRC8_POLYNOMIAL: u8 = 0x31;
-    let mut crc: u8 = u8::MAX;
+    #[cfg(feature = "crc-table")]
+    {
+        crc8_table(data)
+    }
+    #[cfg(not(feature = "crc-table"))]
+    {
+        crc8_bitwise(data)
+    }
+}
+
+/// Calculate the CRC8 checksum bit by bit.
+#[cfg(any(not(feature = "crc-table"), test))]
+#[inline]
+const fn crc8_bitwise(data: &[u8]) -> u8 {
+    crc8_bitwise_with(CRC8_POLYNOMIAL, CRC8_INIT, data)
+}
+
+/// Calculate the CRC8 checksum bit by bit with the given parameters.
+///
+/// Implementation based on the reference implementation by Sensirion.
+#[inline]
+const fn crc8_bitwise_with(poly: u8, init: u8, data: &[u8]) -> u8 {
+    let mut crc: u8 = init;
     let mut i = 0;
 
     while i < data.len() {
         crc ^= data[i];
         i += 1;
 
+        let mut c = 0;
+        while c < 8 {
+            c += 1;
+            if (crc & 0x80) > 0 {
+                crc = (crc << 1) ^ poly;
+            } else {
+                crc <<= 1;
+            }
+        }
+    }
+
+    crc
+}
+
+/// Lookup table of the CRC8 remainder for every possible byte, generated at
+/// compile time.
+#[cfg(any(feature = "crc-table", test))]
+static CRC8_TABLE: [u8; 256] = {
+    let mut table = [0u8; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut crc = i as u8;
         let mut c = 0;
         while c < 8 {
             c += 1;
@@ -20,6 +106,23 @@ pub(crate) const fn crc8(data: &[u8]) -> u8 {
                 crc <<= 1;
             }
         }
+        table[i] = crc;
+        i += 1;
+    }
+
+    table
+};
+
+/// Calculate the CRC8 checksum with a lookup table.
+#[cfg(any(feature = "crc-table", test))]
+#[inline]
+const fn crc8_table(data: &[u8]) -> u8 {
+    let mut crc: u8 = CRC8_INIT;
+    let mut i = 0;
+
+    while i < data.len() {
+        crc = CRC8_TABLE[(crc ^ data[i]) as usize];
+        i += 1;
     }
 
     crc
@@ -29,11 +132,53 @@ pub(crate) const fn crc8(data: &[u8]) -> u8 {
 mod tests {
     use super::*;
 
+    const IMPLEMENTATIONS: [fn(&[u8]) -> u8; 3] = [crc8, crc8_bitwise, crc8_table];
+
     /// Test the crc8 function against the test value provided in the
     /// SHTC3 datasheet (section 5.10).
     #[test]
     fn crc8_test_value() {
-        assert_eq!(crc8(&[0x00]), 0xac);
-        assert_eq!(crc8(&[0xbe, 0xef]), 0x92);
+        for crc8 in IMPLEMENTATIONS {
+            assert_eq!(crc8(&[0x00]), 0xac);
+            assert_eq!(crc8(&[0xbe, 0xef]), 0x92);
+        }
+    }
+
+    /// Test checksums with non-default parameters against the CRC-8/AUTOSAR
+    /// (poly 0x2F, init 0xFF) and CRC-8/SMBUS (poly 0x07, init 0x00) check
+    /// values, without their final XOR.
+    #[test]
+    fn crc8_custom_params() {
+        assert_eq!(Crc8Params::default(), Crc8Params::SENSIRION);
+        assert_eq!(Crc8Params::SENSIRION.checksum(&[0xbe, 0xef]), 0x92);
+
+        let autosar = Crc8Params {
+            poly: 0x2F,
+            init: 0xFF,
+        };
+        assert_eq!(autosar.checksum(b"123456789"), 0xDF ^ 0xFF);
+
+        let smbus = Crc8Params {
+            poly: 0x07,
+            init: 0x00,
+        };
+        assert_eq!(smbus.checksum(b"123456789"), 0xF4);
+
+        // Same polynomial, different init
+        let init_zero = Crc8Params {
+            poly: CRC8_POLYNOMIAL,
+            init: 0x00,
+        };
+        assert_ne!(init_zero.checksum(&[0xbe, 0xef]), 0x92);
+    }
+
+    /// Test that the table driven implementation matches the bitwise one
+    /// for all two byte inputs.
+    #[test]
+    fn crc8_table_matches_bitwise() {
+        for word in 0..=u16::MAX {
+            let bytes = word.to_be_bytes();
+            assert_eq!(crc8_table(&bytes), crc8_bitwise(&bytes));
+        }
     }
 }
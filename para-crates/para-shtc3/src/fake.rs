@@ -0,0 +1,158 @@
+use crate::{
+    DriverError, DynSensor, Measurement, PowerMode, TemperatureHumiditySensor,
+    sensor::forward_dyn_sensor,
+};
+
+/// A fake sensor returning scripted measurement results, for testing code
+/// written against [`TemperatureHumiditySensor`] or [`DynSensor`](crate::DynSensor)
+/// without an I²C mock.
+///
+/// Like the real driver, measurements fail with `DriverError::Asleep` while
+/// the fake is asleep, and results can only be read after starting a
+/// measurement. Reading more results than scripted panics.
+#[derive(Debug)]
+pub struct FakeShtc3<'a> {
+    script: &'a [Result<Measurement, DriverError>],
+    next: usize,
+    started: bool,
+    asleep: bool,
+}
+
+impl<'a> FakeShtc3<'a> {
+    /// Create an awake fake sensor returning the scripted results in order.
+    pub const fn new(script: &'a [Result<Measurement, DriverError>]) -> Self {
+        Self {
+            script,
+            next: 0,
+            started: false,
+            asleep: false,
+        }
+    }
+
+    /// Return whether the fake sensor is asleep.
+    pub const fn is_asleep(&self) -> bool {
+        self.asleep
+    }
+
+    /// Assert that all scripted results were read.
+    pub fn done(&self) {
+        assert_eq!(
+            self.next,
+            self.script.len(),
+            "Not all scripted results were read"
+        );
+    }
+
+    fn check_awake(&self) -> Result<(), DriverError> {
+        if self.asleep {
+            Err(DriverError::Asleep)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl TemperatureHumiditySensor for FakeShtc3<'_> {
+    type Error = DriverError;
+
+    fn start_wakeup(&mut self) -> Result<(), Self::Error> {
+        self.asleep = false;
+        Ok(())
+    }
+
+    fn wakeup_duration(&self) -> u32 {
+        240
+    }
+
+    fn sleep(&mut self) -> Result<(), Self::Error> {
+        self.check_awake()?;
+        self.asleep = true;
+        Ok(())
+    }
+
+    fn start_measurement(&mut self, _mode: PowerMode) -> Result<(), Self::Error> {
+        self.check_awake()?;
+        self.started = true;
+        Ok(())
+    }
+
+    fn max_measurement_duration(&self, mode: PowerMode) -> u32 {
+        match mode {
+            PowerMode::NormalMode => 12_100,
+            PowerMode::LowPower => 800,
+        }
+    }
+
+    fn get_measurement_result(&mut self) -> Result<Measurement, Self::Error> {
+        self.check_awake()?;
+        if !core::mem::take(&mut self.started) {
+            return Err(DriverError::MeasurementNotStarted);
+        }
+
+        let result = self
+            .script
+            .get(self.next)
+            .copied()
+            .expect("No more scripted results");
+        self.next += 1;
+        result
+    }
+
+    fn start_reset(&mut self) -> Result<(), Self::Error> {
+        self.check_awake()?;
+        self.started = false;
+        Ok(())
+    }
+
+    fn reset_duration(&self) -> u32 {
+        240
+    }
+}
+
+impl DynSensor for FakeShtc3<'_> {
+    forward_dyn_sensor!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{Humidity, Temperature};
+
+    const MEASUREMENT: Measurement = Measurement {
+        temperature: Temperature::from_millidegrees_celsius(21_000),
+        humidity: Humidity::from_millipercent(40_000),
+    };
+
+    #[test]
+    fn scripted_results() {
+        let script = [Ok(MEASUREMENT), Err(DriverError::Crc)];
+        let mut fake = FakeShtc3::new(&script);
+        let sensor: &mut dyn DynSensor = &mut fake;
+
+        assert_eq!(
+            sensor.get_measurement_result(),
+            Err(DriverError::MeasurementNotStarted)
+        );
+
+        sensor.start_measurement(PowerMode::LowPower).unwrap();
+        assert_eq!(sensor.get_measurement_result(), Ok(MEASUREMENT));
+        sensor.start_measurement(PowerMode::LowPower).unwrap();
+        assert_eq!(sensor.get_measurement_result(), Err(DriverError::Crc));
+
+        sensor.sleep().unwrap();
+        assert_eq!(
+            sensor.start_measurement(PowerMode::LowPower),
+            Err(DriverError::Asleep)
+        );
+        sensor.start_wakeup().unwrap();
+
+        fake.done();
+    }
+
+    #[test]
+    #[should_panic(expected = "Not all scripted results were read")]
+    fn unread_results() {
+        FakeShtc3::new(&[Ok(MEASUREMENT)]).done();
+    }
+}
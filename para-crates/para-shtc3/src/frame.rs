@@ -0,0 +1,174 @@
+//! Decoding of measurement frames, without any I²C dependency.
+//!
+//! The driver decodes its responses with these functions, so they can also
+//! be used to decode frames captured on the bus, e.g. with a logic analyzer.
+
+use crate::{Crc8Params, MeasurementOrder, RawMeasurement};
+
+/// Errors while decoding a measurement frame.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DecodeError {
+    /// CRC checksum validation failed
+    Crc {
+        /// Index of the 3-byte word that failed validation
+        word_index: usize,
+    },
+}
+
+/// Decode a 6-byte combined measurement frame, validating the CRC8 checksum
+/// of both words.
+///
+/// The order of the temperature and humidity words is given by the
+/// [`MeasurementOrder`] of the measurement command.
+pub fn decode_measurement_frame(
+    buf: &[u8; 6],
+    order: MeasurementOrder,
+) -> Result<RawMeasurement, DecodeError> {
+    decode_measurement_frame_with(buf, order, Some(Crc8Params::SENSIRION))
+}
+
+/// Decode a 3-byte partial measurement frame, validating the CRC8 checksum.
+pub fn decode_partial_frame(buf: &[u8; 3]) -> Result<u16, DecodeError> {
+    decode_partial_frame_with(buf, Some(Crc8Params::SENSIRION))
+}
+
+/// Decode a combined measurement frame, validating the checksums with the
+/// given parameters, or not at all if `crc` is `None`.
+pub(crate) fn decode_measurement_frame_with(
+    buf: &[u8; 6],
+    order: MeasurementOrder,
+    crc: Option<Crc8Params>,
+) -> Result<RawMeasurement, DecodeError> {
+    let first = decode_word(&buf[..3], 0, crc)?;
+    let second = decode_word(&buf[3..], 1, crc)?;
+
+    Ok(match order {
+        MeasurementOrder::TemperatureFirst => RawMeasurement {
+            temperature: first,
+            humidity: second,
+        },
+        MeasurementOrder::HumidityFirst => RawMeasurement {
+            temperature: second,
+            humidity: first,
+        },
+    })
+}
+
+/// Decode a partial measurement frame, validating the checksum with the
+/// given parameters, or not at all if `crc` is `None`.
+pub(crate) fn decode_partial_frame_with(
+    buf: &[u8; 3],
+    crc: Option<Crc8Params>,
+) -> Result<u16, DecodeError> {
+    decode_word(buf, 0, crc)
+}
+
+/// Decode a 3-byte word, validating its checksum unless `crc` is `None`.
+pub(crate) fn decode_word(
+    word: &[u8],
+    word_index: usize,
+    crc: Option<Crc8Params>,
+) -> Result<u16, DecodeError> {
+    if let Some(crc) = crc
+        && crc.checksum(&word[..2]) != word[2]
+    {
+        return Err(DecodeError::Crc { word_index });
+    }
+
+    Ok(u16::from_be_bytes([word[0], word[1]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::crc::crc8;
+
+    #[test]
+    fn measurement_frame() {
+        // Datasheet section 5.4 "Measuring and Reading the Signals"
+        let buf = [0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c];
+
+        assert_eq!(
+            decode_measurement_frame(&buf, MeasurementOrder::TemperatureFirst),
+            Ok(RawMeasurement {
+                temperature: 0x648b,
+                humidity: 0xa133,
+            })
+        );
+        assert_eq!(
+            decode_measurement_frame(&buf, MeasurementOrder::HumidityFirst),
+            Ok(RawMeasurement {
+                temperature: 0xa133,
+                humidity: 0x648b,
+            })
+        );
+        assert_eq!(decode_partial_frame(&[0xa1, 0x33, 0x1c]), Ok(0xa133));
+    }
+
+    #[test]
+    fn crc_error_per_word() {
+        let order = MeasurementOrder::TemperatureFirst;
+
+        assert_eq!(
+            decode_measurement_frame(&[0x64, 0x8b, 0x00, 0xa1, 0x33, 0x1c], order),
+            Err(DecodeError::Crc { word_index: 0 })
+        );
+        assert_eq!(
+            decode_measurement_frame(&[0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x00], order),
+            Err(DecodeError::Crc { word_index: 1 })
+        );
+        assert_eq!(
+            decode_partial_frame(&[0xa1, 0x33, 0x00]),
+            Err(DecodeError::Crc { word_index: 0 })
+        );
+
+        // Validation can be skipped
+        assert_eq!(
+            decode_partial_frame_with(&[0xa1, 0x33, 0x00], None),
+            Ok(0xa133)
+        );
+    }
+
+    /// Test the decoder over pseudo-random byte patterns.
+    #[test]
+    fn arbitrary_frames() {
+        let mut state = 0x1234_5678_u32;
+        let mut next_byte = || {
+            // xorshift32
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        };
+
+        for _ in 0..10_000 {
+            let mut buf = [0u8; 6];
+            buf.iter_mut().for_each(|byte| *byte = next_byte());
+
+            let first_valid = crc8(&buf[..2]) == buf[2];
+            let second_valid = crc8(&buf[3..5]) == buf[5];
+
+            let result = decode_measurement_frame(&buf, MeasurementOrder::TemperatureFirst);
+            match (first_valid, second_valid) {
+                (false, _) => assert_eq!(result, Err(DecodeError::Crc { word_index: 0 })),
+                (true, false) => assert_eq!(result, Err(DecodeError::Crc { word_index: 1 })),
+                (true, true) => assert_eq!(
+                    result,
+                    Ok(RawMeasurement {
+                        temperature: u16::from_be_bytes([buf[0], buf[1]]),
+                        humidity: u16::from_be_bytes([buf[3], buf[4]]),
+                    })
+                ),
+            }
+
+            // Fixing up the checksums always decodes
+            buf[2] = crc8(&buf[..2]);
+            assert_eq!(
+                decode_partial_frame(&[buf[0], buf[1], buf[2]]),
+                Ok(u16::from_be_bytes([buf[0], buf[1]]))
+            );
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Driver logic that doesn't depend on the I²C bus type.
+//!
+//! The generic driver methods delegate to these functions, keeping the checks
+//! and conversions separate from the bus calls.
+//!
+//! The bus calls themselves stay in the generic methods. Routing them through
+//! an object safe bus trait (`&mut dyn Bus`) grew a thumbv7em build with
+//! `opt-level = "z"` and LTO instead, as it prevents inlining and adds an
+//! adapter for every bus type.
+
+use embedded_hal::i2c;
+
+use crate::{
+    Command, Crc8Params, CrcMode, DecodeError, Error, Humidity, SensorKind, Temperature, frame,
+};
+
+/// Errors of the bus independent checks, converted into [`Error`] by the
+/// driver.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum CheckError {
+    Crc { word_index: usize },
+    InvalidLength,
+    Unsupported,
+    Asleep,
+}
+
+impl<E> From<CheckError> for Error<E>
+where
+    E: i2c::Error,
+{
+    fn from(e: CheckError) -> Self {
+        match e {
+            CheckError::Crc { word_index } => Error::Crc { word_index },
+            CheckError::InvalidLength => Error::InvalidLength,
+            CheckError::Unsupported => Error::Unsupported,
+            CheckError::Asleep => Error::Asleep,
+        }
+    }
+}
+
+impl From<DecodeError> for CheckError {
+    fn from(e: DecodeError) -> Self {
+        match e {
+            DecodeError::Crc { word_index } => CheckError::Crc { word_index },
+        }
+    }
+}
+
+/// Check whether the command can be sent to a sensor of the given kind.
+pub(crate) fn check_command(
+    command: Command,
+    kind: SensorKind,
+    asleep: bool,
+) -> Result<(), CheckError> {
+    if !command.is_supported_by(kind) {
+        return Err(CheckError::Unsupported);
+    }
+    if asleep && !matches!(command, Command::WakeUp) {
+        return Err(CheckError::Asleep);
+    }
+    Ok(())
+}
+
+/// Validate the CRC8 checksum of every 3-byte word in the buffer, ignoring
+/// a trailing partial word.
+pub(crate) fn validate_crc(crc_params: &Crc8Params, buf: &[u8]) -> Result<(), CheckError> {
+    let mut chunks = buf.chunks_exact(3);
+
+    for (word_index, chunk) in chunks.by_ref().enumerate() {
+        frame::decode_word(chunk, word_index, Some(*crc_params))?;
+    }
+
+    #[cfg(feature = "defmt")]
+    if !chunks.remainder().is_empty() {
+        defmt::warn!("Remaining data in buffer was not CRC8 validated");
+    }
+
+    Ok(())
+}
+
+/// Validate the CRC8 checksum of the buffer, which must be a whole number of
+/// 3-byte words.
+pub(crate) fn validate_crc_strict(crc_params: &Crc8Params, buf: &[u8]) -> Result<(), CheckError> {
+    if !buf.len().is_multiple_of(3) {
+        return Err(CheckError::InvalidLength);
+    }
+
+    validate_crc(crc_params, buf)
+}
+
+/// Validate the CRC8 checksum of the buffer according to the [`CrcMode`].
+pub(crate) fn check_crc(
+    crc_mode: CrcMode,
+    crc_params: &Crc8Params,
+    buf: &[u8],
+) -> Result<(), CheckError> {
+    match crc_mode {
+        CrcMode::Strict => validate_crc(crc_params, buf),
+        CrcMode::StrictLength => validate_crc_strict(crc_params, buf),
+        CrcMode::Skip => Ok(()),
+    }
+}
+
+/// Convert a raw temperature and apply the calibration offset.
+pub(crate) fn calibrated_temperature(raw: u16, offset_millidegrees: i32) -> Temperature {
+    Temperature::from_millidegrees_celsius(
+        Temperature::from_raw(raw)
+            .as_millidegrees_celsius()
+            .saturating_add(offset_millidegrees),
+    )
+}
+
+/// Convert a raw humidity and apply the calibration offset.
+pub(crate) fn calibrated_humidity(raw: u16, offset_millipercent: i32) -> Humidity {
+    Humidity::from_millipercent(
+        Humidity::from_raw(raw)
+            .as_millipercent()
+            .saturating_add(offset_millipercent),
+    )
+}
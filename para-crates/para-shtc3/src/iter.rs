@@ -0,0 +1,152 @@
+use embedded_hal::{
+    delay::DelayNs,
+    i2c::{I2c, SevenBitAddress},
+};
+
+use crate::{Error, Measurement, MeasurementAccumulator, PowerMode, ShtC3};
+
+/// An endless iterator of blocking measurements, created by
+/// [`ShtC3::measurements`].
+pub struct Measurements<'a, I2C, D> {
+    sht: &'a mut ShtC3<I2C>,
+    mode: PowerMode,
+    delay: &'a mut D,
+}
+
+impl<'a, I2C, D> Measurements<'a, I2C, D> {
+    pub(crate) fn new(sht: &'a mut ShtC3<I2C>, mode: PowerMode, delay: &'a mut D) -> Self {
+        Self { sht, mode, delay }
+    }
+}
+
+impl<I2C, D> Iterator for Measurements<'_, I2C, D>
+where
+    I2C: I2c<SevenBitAddress>,
+    D: DelayNs,
+{
+    type Item = Result<Measurement, Error<I2C::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.sht.measure(self.mode, self.delay))
+    }
+}
+
+/// An iterator adapter yielding the moving average over the last `N`
+/// measurements, created by [`MeasurementsExt::moving_average`].
+///
+/// Errors are passed through without affecting the window. Until `N`
+/// measurements were seen, the average over the available ones is returned.
+pub struct MovingAverage<I, const N: usize> {
+    iter: I,
+    window: [Measurement; N],
+    len: usize,
+    next: usize,
+}
+
+impl<I, E, const N: usize> Iterator for MovingAverage<I, N>
+where
+    I: Iterator<Item = Result<Measurement, E>>,
+{
+    type Item = Result<Measurement, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let measurement = match self.iter.next()? {
+            Ok(measurement) => measurement,
+            Err(e) => return Some(Err(e)),
+        };
+
+        self.window[self.next] = measurement;
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+
+        let mut acc = MeasurementAccumulator::new();
+        for &measurement in &self.window[..self.len] {
+            acc.add(measurement);
+        }
+
+        Some(Ok(acc.mean().unwrap_or(measurement)))
+    }
+}
+
+/// Extension methods for iterators of measurement results.
+pub trait MeasurementsExt<E>: Iterator<Item = Result<Measurement, E>> + Sized {
+    /// Smooth the measurements with a moving average over the last `N`
+    /// measurements.
+    fn moving_average<const N: usize>(self) -> MovingAverage<Self, N> {
+        const { assert!(N > 0, "The moving average window must not be empty") };
+
+        MovingAverage {
+            iter: self,
+            window: [Measurement::default(); N],
+            len: 0,
+            next: 0,
+        }
+    }
+}
+
+impl<I, E> MeasurementsExt<E> for I where I: Iterator<Item = Result<Measurement, E>> {}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use super::*;
+
+    use crate::{Humidity, Temperature, tests::SHT_ADDR};
+
+    use embedded_hal_mock::eh1::{
+        delay::NoopDelay,
+        i2c::{Mock as I2cMock, Transaction},
+    };
+
+    fn measurement(millidegrees: i32) -> Result<Measurement, ()> {
+        Ok(Measurement {
+            temperature: Temperature::from_millidegrees_celsius(millidegrees),
+            humidity: Humidity::from_millipercent(50_000),
+        })
+    }
+
+    #[test]
+    fn measurements() {
+        let expectations = [
+            Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+            Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+            Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+            Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+        ];
+        let mut sht = ShtC3::new(I2cMock::new(&expectations));
+
+        for measurement in sht
+            .measurements(PowerMode::LowPower, &mut NoopDelay)
+            .take(2)
+        {
+            assert_eq!(
+                measurement.unwrap().temperature.as_millidegrees_celsius(),
+                23_730
+            );
+        }
+        sht.destroy().done();
+    }
+
+    #[test]
+    fn moving_average() {
+        let readings = [
+            measurement(20_000),
+            measurement(22_000),
+            Err(()),
+            measurement(24_000),
+            measurement(30_000),
+        ];
+
+        let averages: alloc::vec::Vec<_> = readings
+            .into_iter()
+            .moving_average::<3>()
+            .map(|m| m.map(|m| m.temperature.as_millidegrees_celsius()))
+            .collect();
+
+        assert_eq!(
+            averages,
+            [Ok(20_000), Ok(21_000), Err(()), Ok(22_000), Ok(25_333)]
+        );
+    }
+}
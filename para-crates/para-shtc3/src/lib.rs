@@ -38,6 +38,25 @@
 //! let mut sht = ShtC3::new(dev);
 //! ```
 //!
+//! ### Sharing the Bus
+//!
+//! The driver takes ownership of anything implementing the `I2c` trait, so
+//! the bus can be shared with other devices by using the device wrappers
+//! from [`embedded-hal-bus`](https://docs.rs/embedded-hal-bus), such as
+//! `RefCellDevice`, `CriticalSectionDevice` or `AtomicDevice`. A mutable
+//! reference to a bus also implements `I2c`, for borrowing it temporarily.
+//!
+//! ```no_run
+//! use core::cell::RefCell;
+//! use embedded_hal_bus::i2c::RefCellDevice;
+//! use linux_embedded_hal::I2cdev;
+//! use para_shtc3::ShtC3;
+//!
+//! let bus = RefCell::new(I2cdev::new("/dev/i2c-1").unwrap());
+//! let mut sht = ShtC3::new(RefCellDevice::new(&bus));
+//! // Other devices can be created with `RefCellDevice::new(&bus)` as well
+//! ```
+//!
 //! ### Device Info
 //!
 //! Then, you can query information about the sensor:
@@ -136,7 +155,16 @@
 #![deny(unsafe_code, missing_docs)]
 #![no_std]
 
+pub mod conversion;
 mod crc;
+#[cfg(feature = "test-util")]
+mod fake;
+mod frame;
+mod inner;
+mod iter;
+mod pending;
+mod sensor;
+mod session;
 mod types;
 
 use embedded_hal::{
@@ -144,14 +172,132 @@ use embedded_hal::{
     i2c::{self, I2c, SevenBitAddress},
 };
 
-use crc::crc8;
+pub use crc::Crc8Params;
+#[cfg(feature = "test-util")]
+pub use fake::FakeShtc3;
+pub use frame::{DecodeError, decode_measurement_frame, decode_partial_frame};
+pub use iter::{Measurements, MeasurementsExt, MovingAverage};
+pub use pending::Pending;
+pub use sensor::*;
+pub use session::AwakeSession;
 pub use types::*;
 
-/// Whether temperature or humidity is returned first when doing a measurement.
+/// The default I²C address of the SHTC3.
+const SHTC3_ADDRESS: SevenBitAddress = 0x70;
+
+/// The sensor variant driven by [`ShtC3`].
+///
+/// The SHTC1 and SHTW2 share the SHTC3's protocol, but differ in their
+/// device identifier and the commands they support.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SensorKind {
+    /// The SHTC3.
+    #[default]
+    Shtc3,
+    /// The SHTC1, which has neither a sleep mode nor a low power mode.
+    Shtc1,
+    /// The SHTW2.
+    Shtw2,
+}
+
+impl SensorKind {
+    /// Return the default I²C address of the sensor.
+    pub const fn default_address(self) -> SevenBitAddress {
+        SHTC3_ADDRESS
+    }
+
+    /// Return the mask of the identifier bits in the ID register.
+    pub const fn identifier_mask(self) -> u16 {
+        match self {
+            SensorKind::Shtc3 => 0b0000_1000_0011_1111,
+            SensorKind::Shtc1 | SensorKind::Shtw2 => 0b0000_0000_0011_1111,
+        }
+    }
+
+    /// Return the expected 7-bit device identifier of the sensor.
+    pub const fn identifier(self) -> u8 {
+        match self {
+            SensorKind::Shtc3 => 0x47,
+            SensorKind::Shtc1 | SensorKind::Shtw2 => 0x07,
+        }
+    }
+
+    /// Whether the sensor supports the sleep and wakeup commands.
+    pub const fn supports_sleep(self) -> bool {
+        !matches!(self, SensorKind::Shtc1)
+    }
+
+    /// Whether the sensor supports low power measurements.
+    pub const fn supports_low_power(self) -> bool {
+        !matches!(self, SensorKind::Shtc1)
+    }
+}
+
+/// The contents of the ID register, returned by [`ShtC3::id_register`].
+///
+/// The SHTC3 ID register is laid out as `xxxx 1xxx xx00 0111` (SHTC3
+/// datasheet 5.9), where the `x` bits are Sensirion-internal and may differ
+/// between silicon revisions. The SHTC1 and SHTW2 only use the lower 6 bits
+/// for their identifier.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-enum MeasurementOrder {
+pub struct IdRegister {
+    raw: u16,
+    kind: SensorKind,
+}
+
+impl IdRegister {
+    /// Create a new `IdRegister` from the raw register value read from a
+    /// sensor of the given kind.
+    pub const fn new(raw: u16, kind: SensorKind) -> Self {
+        Self { raw, kind }
+    }
+
+    /// Return the raw 16-bit register value.
+    pub const fn raw(&self) -> u16 {
+        self.raw
+    }
+
+    /// Return the 7-bit device identifier, built from bit 11 and bits 5-0.
+    ///
+    /// Should be 0x47 (71) for the SHTC3, and 0x07 for the SHTC1 and SHTW2.
+    pub const fn identifier(&self) -> u8 {
+        let ident = self.raw & self.kind.identifier_mask();
+        let lsb = (ident & 0b0011_1111) as u8;
+        let msb = ((ident & 0b0000_1000_0000_0000) >> 5) as u8;
+        lsb | msb
+    }
+
+    /// Return the Sensirion-internal bits 15-12.
+    pub const fn reserved_upper(&self) -> u8 {
+        (self.raw >> 12) as u8
+    }
+
+    /// Return the Sensirion-internal bits 10-6.
+    pub const fn reserved_middle(&self) -> u8 {
+        ((self.raw >> 6) & 0b1_1111) as u8
+    }
+
+    /// Whether the register identifies an SHTC3, regardless of the sensor
+    /// kind the driver was configured with.
+    pub const fn is_shtc3(&self) -> bool {
+        Self::new(self.raw, SensorKind::Shtc3).identifier() == SensorKind::Shtc3.identifier()
+    }
+}
+
+/// Whether temperature or humidity is returned first when doing a measurement.
+///
+/// The order determines which value is read by a partial read such as
+/// [`get_raw_partial_measurement_result`](crate::ShtC3::get_raw_partial_measurement_result()),
+/// as only the first 3-byte word of the measurement response is read.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MeasurementOrder {
+    /// Temperature is returned first, followed by humidity.
+    #[default]
     TemperatureFirst,
+    /// Humidity is returned first, followed by temperature.
     HumidityFirst,
 }
 
@@ -168,32 +314,291 @@ enum MeasurementOrder {
 /// note][an-low-power] by Sensirion.
 ///
 /// [an-low-power]: https://www.sensirion.com/fileadmin/user_upload/customers/sensirion/Dokumente/2_Humidity_Sensors/Sensirion_Humidity_Sensors_SHTC3_Low_Power_Measurement_Mode.pdf
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum PowerMode {
     /// Normal measurement.
+    #[default]
     NormalMode,
     /// Low power measurement: Less energy consumption, but repeatability and
     /// accuracy of measurements are negatively impacted.
     LowPower,
 }
 
+/// CRC validation mode for data read from the sensor.
+///
+/// Skipping validation saves the cycles spent on checksums, at the cost of
+/// no longer detecting corrupted data on the bus. Only use
+/// [`CrcMode::Skip`] on short, well-shielded buses.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CrcMode {
+    /// Validate the CRC8 checksum of every word read from the sensor.
+    #[default]
+    Strict,
+    /// Validate the CRC8 checksum of every word read from the sensor, and
+    /// reject any read that is not a whole number of 3-byte words.
+    StrictLength,
+    /// Read the checksum bytes but do not validate them.
+    Skip,
+}
+
+/// The settings a measurement was taken with, as returned by
+/// [`ShtC3::measure_tagged`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MeasurementProvenance {
+    /// The power mode of the measurement.
+    pub power_mode: PowerMode,
+    /// The order in which the sensor returned the values.
+    pub order: MeasurementOrder,
+}
+
+/// Reusable settings for [`ShtC3::measure_with`].
+///
+/// ```
+/// use para_shtc3::{CrcMode, MeasureConfig, PowerMode};
+///
+/// const CONFIG: MeasureConfig = MeasureConfig::new()
+///     .power_mode(PowerMode::LowPower)
+///     .samples(4)
+///     .margin_percent(10)
+///     .crc_mode(CrcMode::StrictLength);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MeasureConfig {
+    power_mode: PowerMode,
+    samples: u8,
+    margin_percent: u32,
+    crc_mode: CrcMode,
+}
+
+impl MeasureConfig {
+    /// Create a configuration for a single normal mode measurement without
+    /// timing margin and with strict CRC validation.
+    pub const fn new() -> Self {
+        Self {
+            power_mode: PowerMode::NormalMode,
+            samples: 1,
+            margin_percent: 0,
+            crc_mode: CrcMode::Strict,
+        }
+    }
+
+    /// Set the power mode of the measurements.
+    pub const fn power_mode(mut self, power_mode: PowerMode) -> Self {
+        self.power_mode = power_mode;
+        self
+    }
+
+    /// Set the number of measurements to average. A count of 0 is treated
+    /// as 1.
+    pub const fn samples(mut self, samples: u8) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    /// Set the additional time (in percent) to wait for each measurement.
+    pub const fn margin_percent(mut self, margin_percent: u32) -> Self {
+        self.margin_percent = margin_percent;
+        self
+    }
+
+    /// Set the CRC validation mode used for the measurements.
+    pub const fn crc_mode(mut self, crc_mode: CrcMode) -> Self {
+        self.crc_mode = crc_mode;
+        self
+    }
+}
+
+impl Default for MeasureConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How a command and its response are transferred on the I²C bus.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TransactionMode {
+    /// Send the command and read the response as separate transactions.
+    #[default]
+    Split,
+    /// Send the command and read the response in a single `write_read`
+    /// transaction with a repeated start, where the sensor supports it.
+    ///
+    /// Only use this with HALs that support repeated start conditions.
+    Combined,
+}
+
+/// The operation during which an I²C bus error occurred.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Operation {
+    /// Sending the wakeup command, or polling the sensor while it wakes up
+    WakeUp,
+    /// Sending the sleep command
+    Sleep,
+    /// Sending a measurement command
+    Measure,
+    /// Reading a measurement result
+    ReadResult,
+    /// Reading the ID register
+    ReadId,
+    /// Sending the soft reset command
+    Reset,
+    /// An operation not known to the driver, e.g. for bus errors converted
+    /// with `From`
+    Unknown,
+}
+
 /// All possible errors in this crate
 #[derive(Debug, PartialEq, Clone)]
-#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Error<E: i2c::Error> {
     /// I²C bus error
-    I2c(E),
+    I2c {
+        /// The operation that failed
+        op: Operation,
+        /// The error returned by the I²C bus
+        source: E,
+    },
     /// CRC checksum validation failed
-    Crc,
+    Crc {
+        /// Index of the 3-byte word that failed validation
+        word_index: usize,
+    },
+    /// The device identifier did not match the expected identifier of the
+    /// sensor kind
+    UnexpectedDevice {
+        /// The device identifier that was read from the sensor
+        got: u8,
+    },
+    /// The data read from the sensor was not a whole number of 3-byte words
+    InvalidLength,
+    /// The command is not supported by the sensor kind
+    Unsupported,
+    /// The sensor returned a reading outside of its physical operating range
+    ImplausibleReading(Measurement),
+    /// A command other than wakeup was sent while the sensor is asleep
+    Asleep,
+    /// The sensor did not respond within the wakeup duration
+    WakeupTimeout,
+    /// The measurement was not finished within the maximum measurement
+    /// duration
+    Timeout,
+    /// A measurement result was read without starting a matching measurement
+    MeasurementNotStarted,
+    /// Two measurements of the same conditions disagreed by more than the
+    /// allowed tolerance
+    Inconsistent {
+        /// The measurement taken in temperature first order
+        temperature_first: Measurement,
+        /// The measurement taken in humidity first order
+        humidity_first: Measurement,
+    },
 }
 
+#[cfg(feature = "defmt")]
+impl<E: i2c::Error> defmt::Format for Error<E> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        match self {
+            Error::I2c { op, source } => {
+                defmt::write!(f, "I2c {{ op: {}, source: {} }}", op, source.kind())
+            }
+            Error::Crc { word_index } => defmt::write!(f, "Crc {{ word_index: {} }}", word_index),
+            Error::UnexpectedDevice { got } => {
+                defmt::write!(f, "UnexpectedDevice {{ got: {=u8:#x} }}", got)
+            }
+            Error::InvalidLength => defmt::write!(f, "InvalidLength"),
+            Error::Unsupported => defmt::write!(f, "Unsupported"),
+            Error::Asleep => defmt::write!(f, "Asleep"),
+            Error::WakeupTimeout => defmt::write!(f, "WakeupTimeout"),
+            Error::Timeout => defmt::write!(f, "Timeout"),
+            Error::MeasurementNotStarted => defmt::write!(f, "MeasurementNotStarted"),
+            Error::ImplausibleReading(m) => defmt::write!(f, "ImplausibleReading({})", m),
+            Error::Inconsistent {
+                temperature_first,
+                humidity_first,
+            } => defmt::write!(
+                f,
+                "Inconsistent {{ temperature_first: {}, humidity_first: {} }}",
+                temperature_first,
+                humidity_first
+            ),
+        }
+    }
+}
+
+impl<E> Error<E>
+where
+    E: i2c::Error,
+{
+    /// Return the operation that failed, if this is an I²C bus error.
+    pub fn operation(&self) -> Option<Operation> {
+        match self {
+            Error::I2c { op, .. } => Some(*op),
+            _ => None,
+        }
+    }
+
+    /// Return the kind of the I²C bus error, if this is one.
+    ///
+    /// This allows handling bus errors without knowing the concrete error
+    /// type of the bus, e.g. retrying on arbitration loss.
+    pub fn kind(&self) -> Option<i2c::ErrorKind> {
+        match self {
+            Error::I2c { source, .. } => Some(source.kind()),
+            _ => None,
+        }
+    }
+}
+
+impl<E> core::fmt::Display for Error<E>
+where
+    E: i2c::Error,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::I2c { op, source } => {
+                write!(f, "I2C error during {op:?}: {}", source.kind())
+            }
+            Error::Crc { word_index } => write!(f, "CRC mismatch in word {word_index}"),
+            Error::UnexpectedDevice { got } => write!(f, "unexpected device identifier {got:#x}"),
+            Error::InvalidLength => f.write_str("data is not a whole number of words"),
+            Error::Unsupported => f.write_str("command not supported by the sensor"),
+            Error::ImplausibleReading(_) => f.write_str("implausible reading"),
+            Error::Asleep => f.write_str("sensor is asleep"),
+            Error::WakeupTimeout => f.write_str("sensor did not wake up in time"),
+            Error::Timeout => f.write_str("measurement did not finish in time"),
+            Error::MeasurementNotStarted => f.write_str("measurement not started"),
+            Error::Inconsistent { .. } => f.write_str("inconsistent measurements"),
+        }
+    }
+}
+
+impl<E> core::error::Error for Error<E> where E: i2c::Error {}
+
 impl<E> From<E> for Error<E>
 where
     E: i2c::Error,
 {
-    fn from(e: E) -> Self {
-        Error::I2c(e)
+    fn from(source: E) -> Self {
+        Error::I2c {
+            op: Operation::Unknown,
+            source,
+        }
+    }
+}
+
+impl<E> From<DecodeError> for Error<E>
+where
+    E: i2c::Error,
+{
+    fn from(e: DecodeError) -> Self {
+        match e {
+            DecodeError::Crc { word_index } => Error::Crc { word_index },
+        }
     }
 }
 
@@ -241,6 +646,52 @@ impl Command {
             Command::SoftwareReset => [0x80, 0x5D],
         }
     }
+
+    /// The operation of sending this command.
+    fn operation(self) -> Operation {
+        match self {
+            Command::Sleep => Operation::Sleep,
+            Command::WakeUp => Operation::WakeUp,
+            Command::Measure { .. } => Operation::Measure,
+            Command::SoftwareReset => Operation::Reset,
+            Command::ReadIdRegister => Operation::ReadId,
+        }
+    }
+
+    /// The operation of reading the response to this command.
+    fn read_operation(self) -> Operation {
+        match self {
+            Command::ReadIdRegister => Operation::ReadId,
+            _ => Operation::ReadResult,
+        }
+    }
+
+    const fn is_supported_by(self, kind: SensorKind) -> bool {
+        match self {
+            Command::Sleep | Command::WakeUp => kind.supports_sleep(),
+            Command::Measure {
+                power_mode: PowerMode::LowPower,
+                ..
+            } => kind.supports_low_power(),
+            Command::Measure { .. } | Command::SoftwareReset | Command::ReadIdRegister => true,
+        }
+    }
+}
+
+/// The kind of measurement started, and not yet read.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+enum StartedMeasurement {
+    /// No measurement was started.
+    #[default]
+    None,
+    /// A measurement of which only the first value is read.
+    Partial,
+    /// A measurement of which both values can be read.
+    Combined,
+    /// A measurement of which the first value was read, and the second value
+    /// can still be read.
+    Remaining,
 }
 
 /// Driver for the SHTC3 sensor.
@@ -251,6 +702,71 @@ pub struct ShtC3<I2C> {
     i2c: I2C,
     /// The I²C device address.
     address: u8,
+    /// The sensor variant.
+    kind: SensorKind,
+    /// Whether to validate the CRC of data read from the sensor.
+    crc_mode: CrcMode,
+    /// The parameters of the CRC8 checksum.
+    crc_params: Crc8Params,
+    /// Whether to combine commands and responses into a single transaction.
+    transaction_mode: TransactionMode,
+    /// The power mode of the last started measurement.
+    mode: PowerMode,
+    /// The order of the last started measurement.
+    order: MeasurementOrder,
+    /// The measurement that was started, if its result wasn't read yet.
+    started: StartedMeasurement,
+    /// Whether the sensor was put into sleep mode.
+    asleep: bool,
+    /// Offset added to temperature results, in milli-degrees celsius.
+    temperature_offset: i32,
+    /// Offset added to humidity results, in 1/1000 %RH.
+    humidity_offset: i32,
+}
+
+/// The configuration and sensor state of a driver instance, without the I²C
+/// bus.
+///
+/// Returned by [`ShtC3::into_parts`] so a driver can be rebuilt with
+/// [`ShtC3::from_parts`] over a bus that is only created for each
+/// measurement cycle, e.g. to power gate the peripheral, without losing the
+/// calibration offsets or the sleep tracking. A measurement that was started
+/// but not read is not kept.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ShtC3State {
+    /// The I²C device address.
+    pub address: SevenBitAddress,
+    /// The sensor variant.
+    pub kind: SensorKind,
+    /// Whether to validate the CRC of data read from the sensor.
+    pub crc_mode: CrcMode,
+    /// The parameters of the CRC8 checksum.
+    pub crc_params: Crc8Params,
+    /// Whether to combine commands and responses into a single transaction.
+    pub transaction_mode: TransactionMode,
+    /// Whether the sensor was put into sleep mode.
+    pub asleep: bool,
+    /// Offset added to temperature results, in milli-degrees celsius.
+    pub temperature_offset: i32,
+    /// Offset added to humidity results, in 1/1000 %RH.
+    pub humidity_offset: i32,
+}
+
+/// Timing constants, usable in `const` contexts without a driver instance.
+impl<I2C> ShtC3<I2C> {
+    /// Wakeup delay duration in microseconds.
+    pub const WAKEUP_DURATION_US: u32 = 240;
+    /// Soft reset duration in microseconds (SHTC3 datasheet 3.1).
+    pub const RESET_DURATION_US: u32 = 240;
+    /// Maximum normal mode measurement duration in microseconds.
+    pub const MEASURE_NORMAL_US: u32 = 12100;
+    /// Maximum low power mode measurement duration in microseconds.
+    pub const MEASURE_LOWPOWER_US: u32 = 800;
+    /// Typical normal mode measurement duration in microseconds.
+    pub const MEASURE_NORMAL_TYPICAL_US: u32 = 10800;
+    /// Typical low power mode measurement duration in microseconds.
+    pub const MEASURE_LOWPOWER_TYPICAL_US: u32 = 700;
 }
 
 /// General functions.
@@ -261,13 +777,155 @@ where
     /// Create a new instance of the driver for the SHTC3.
     #[inline]
     pub const fn new(i2c: I2C) -> Self {
-        Self { i2c, address: 0x70 }
+        Self::with_kind(i2c, SensorKind::Shtc3)
+    }
+
+    /// Create a new instance of the driver for the given sensor variant,
+    /// using its default address.
+    #[inline]
+    pub const fn with_kind(i2c: I2C, kind: SensorKind) -> Self {
+        Self::with_config(i2c, kind, kind.default_address(), CrcMode::Strict)
+    }
+
+    /// Create a new instance of the driver for the SHTC3 with the given CRC
+    /// validation mode.
+    #[inline]
+    pub const fn new_with_crc_mode(i2c: I2C, crc_mode: CrcMode) -> Self {
+        Self::with_config(i2c, SensorKind::Shtc3, SHTC3_ADDRESS, crc_mode)
+    }
+
+    /// Create a new instance of the driver for an SHTC3 at a non-default
+    /// I²C address, such as one behind an address translator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the address is not a valid 7-bit I²C address.
+    #[inline]
+    pub const fn new_with_address(i2c: I2C, address: SevenBitAddress) -> Self {
+        assert!(address <= 0x7F, "Invalid 7-bit I²C address");
+        Self::with_config(i2c, SensorKind::Shtc3, address, CrcMode::Strict)
+    }
+
+    #[inline]
+    const fn with_config(
+        i2c: I2C,
+        kind: SensorKind,
+        address: SevenBitAddress,
+        crc_mode: CrcMode,
+    ) -> Self {
+        Self {
+            i2c,
+            address,
+            kind,
+            crc_mode,
+            crc_params: Crc8Params::SENSIRION,
+            transaction_mode: TransactionMode::Split,
+            mode: PowerMode::NormalMode,
+            order: MeasurementOrder::TemperatureFirst,
+            started: StartedMeasurement::None,
+            asleep: false,
+            temperature_offset: 0,
+            humidity_offset: 0,
+        }
+    }
+
+    /// Create a new instance of the driver with the given CRC8 parameters,
+    /// for clones whose checksum differs from the datasheet.
+    #[inline]
+    pub const fn new_with_crc_params(i2c: I2C, crc_params: Crc8Params) -> Self {
+        let mut sht = Self::new(i2c);
+        sht.crc_params = crc_params;
+        sht
+    }
+
+    /// Return the CRC8 parameters used for validation.
+    #[inline]
+    pub const fn crc_params(&self) -> Crc8Params {
+        self.crc_params
+    }
+
+    /// Set the CRC8 parameters used for validation.
+    #[inline]
+    pub fn set_crc_params(&mut self, crc_params: Crc8Params) {
+        self.crc_params = crc_params;
+    }
+
+    /// Return the current CRC validation mode.
+    #[inline]
+    pub const fn crc_mode(&self) -> CrcMode {
+        self.crc_mode
+    }
+
+    /// Set the CRC validation mode.
+    #[inline]
+    pub fn set_crc_mode(&mut self, crc_mode: CrcMode) {
+        self.crc_mode = crc_mode;
+    }
+
+    /// Return the current transaction mode.
+    #[inline]
+    pub const fn transaction_mode(&self) -> TransactionMode {
+        self.transaction_mode
+    }
+
+    /// Set the transaction mode used for reading registers.
+    #[inline]
+    pub fn set_transaction_mode(&mut self, transaction_mode: TransactionMode) {
+        self.transaction_mode = transaction_mode;
+    }
+
+    /// Return the calibration offsets as milli-degrees celsius and
+    /// 1/1000 %RH.
+    #[inline]
+    pub const fn calibration(&self) -> (i32, i32) {
+        (self.temperature_offset, self.humidity_offset)
+    }
+
+    /// Set calibration offsets that are added to every temperature and
+    /// humidity result.
+    ///
+    /// The offsets saturate instead of overflowing, and corrected humidity
+    /// values are clamped to 0-100 %RH. Raw results are not affected.
+    pub fn set_calibration(&mut self, offset_millidegrees: i32, offset_millipercent: i32) {
+        self.temperature_offset = offset_millidegrees;
+        self.humidity_offset = offset_millipercent;
     }
 
     /// Get the device's wakeup delay duration in microseconds
     #[inline(always)]
     pub const fn wakeup_duration(&self) -> u32 {
-        240
+        Self::WAKEUP_DURATION_US
+    }
+
+    /// Get the device's wakeup delay duration.
+    ///
+    /// ```
+    /// # use embedded_hal::i2c::I2c;
+    /// # use para_shtc3::ShtC3;
+    /// use embassy_time::Timer;
+    ///
+    /// async fn wakeup<I2C: I2c>(sht: &mut ShtC3<I2C>) -> Result<(), para_shtc3::Error<I2C::Error>> {
+    ///     sht.start_wakeup()?;
+    ///     Timer::after_micros(sht.wakeup_duration_t().to_micros().into()).await;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "fugit")]
+    #[inline(always)]
+    pub const fn wakeup_duration_t(&self) -> fugit::MicrosDurationU32 {
+        fugit::MicrosDurationU32::micros(Self::WAKEUP_DURATION_US)
+    }
+
+    /// Return the I²C device address.
+    #[inline]
+    pub const fn address(&self) -> SevenBitAddress {
+        self.address
+    }
+
+    /// Return the sensor variant.
+    #[inline]
+    pub const fn kind(&self) -> SensorKind {
+        self.kind
     }
 
     /// Destroy driver instance, return I²C bus instance.
@@ -275,6 +933,46 @@ where
         self.i2c
     }
 
+    /// Destroy driver instance, return I²C bus instance and the driver
+    /// state, which can be passed to [`from_parts`](Self::from_parts).
+    pub fn into_parts(self) -> (I2C, ShtC3State) {
+        let state = ShtC3State {
+            address: self.address,
+            kind: self.kind,
+            crc_mode: self.crc_mode,
+            crc_params: self.crc_params,
+            transaction_mode: self.transaction_mode,
+            asleep: self.asleep,
+            temperature_offset: self.temperature_offset,
+            humidity_offset: self.humidity_offset,
+        };
+        (self.i2c, state)
+    }
+
+    /// Create a driver instance from an I²C bus instance and the state
+    /// returned by [`into_parts`](Self::into_parts).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the address is not a valid 7-bit I²C address.
+    pub const fn from_parts(i2c: I2C, state: ShtC3State) -> Self {
+        assert!(state.address <= 0x7F, "Invalid 7-bit I²C address");
+        Self {
+            i2c,
+            address: state.address,
+            kind: state.kind,
+            crc_mode: state.crc_mode,
+            crc_params: state.crc_params,
+            transaction_mode: state.transaction_mode,
+            mode: PowerMode::NormalMode,
+            order: MeasurementOrder::TemperatureFirst,
+            started: StartedMeasurement::None,
+            asleep: state.asleep,
+            temperature_offset: state.temperature_offset,
+            humidity_offset: state.humidity_offset,
+        }
+    }
+
     /// Return the maximum measurement duration (depending on the mode) in
     /// microseconds.
     ///
@@ -284,74 +982,228 @@ where
     #[inline(always)]
     pub const fn max_measurement_duration(&self, mode: PowerMode) -> u32 {
         match mode {
-            PowerMode::NormalMode => 12100,
-            PowerMode::LowPower => 800,
+            PowerMode::NormalMode => Self::MEASURE_NORMAL_US,
+            PowerMode::LowPower => Self::MEASURE_LOWPOWER_US,
+        }
+    }
+
+    /// Return the maximum measurement duration (depending on the mode).
+    ///
+    /// ```
+    /// # use embedded_hal::i2c::I2c;
+    /// # use para_shtc3::{Measurement, PowerMode, ShtC3};
+    /// use embassy_time::Timer;
+    ///
+    /// async fn measure<I2C: I2c>(
+    ///     sht: &mut ShtC3<I2C>,
+    /// ) -> Result<Measurement, para_shtc3::Error<I2C::Error>> {
+    ///     let mode = PowerMode::LowPower;
+    ///     sht.start_measurement(mode)?;
+    ///     Timer::after_micros(sht.max_measurement_duration_t(mode).to_micros().into()).await;
+    ///     sht.get_measurement_result()
+    /// }
+    /// ```
+    #[cfg(feature = "fugit")]
+    #[inline(always)]
+    pub const fn max_measurement_duration_t(&self, mode: PowerMode) -> fugit::MicrosDurationU32 {
+        fugit::MicrosDurationU32::micros(self.max_measurement_duration(mode))
+    }
+
+    /// Return the typical measurement duration (depending on the mode) in
+    /// microseconds.
+    ///
+    /// Typical measurement duration (SHTC3 datasheet 3.1):
+    /// - Normal mode: 10.8 ms
+    /// - Low power mode: 0.7 ms
+    #[inline(always)]
+    pub const fn typical_measurement_duration(&self, mode: PowerMode) -> u32 {
+        match mode {
+            PowerMode::NormalMode => Self::MEASURE_NORMAL_TYPICAL_US,
+            PowerMode::LowPower => Self::MEASURE_LOWPOWER_TYPICAL_US,
+        }
+    }
+
+    /// Return the maximum measurement duration (depending on the mode) in
+    /// microseconds, extended by a safety margin given in percent.
+    ///
+    /// Useful when the local clock is inaccurate (such as an RC oscillator),
+    /// so waiting exactly the datasheet maximum could still read before the
+    /// sensor is done.
+    #[inline(always)]
+    pub const fn max_measurement_duration_with_margin(
+        &self,
+        mode: PowerMode,
+        margin_percent: u32,
+    ) -> u32 {
+        let duration = self.max_measurement_duration(mode);
+        duration.saturating_add(duration.saturating_mul(margin_percent) / 100)
+    }
+
+    /// Check whether the command can be sent to the sensor.
+    ///
+    /// Returns `Error::Unsupported` if the command isn't supported by the
+    /// sensor kind, and `Error::Asleep` for any command other than wakeup
+    /// while the sensor is asleep.
+    fn check_command(&self, command: Command) -> Result<(), Error<I2C::Error>> {
+        Ok(inner::check_command(command, self.kind, self.asleep)?)
+    }
+
+    /// Prepare sending a command, see [`check_command`](Self::check_command).
+    ///
+    /// Called before any command is sent, regardless of the transaction mode.
+    fn prepare_command(&mut self, command: Command) -> Result<(), Error<I2C::Error>> {
+        self.check_command(command)?;
+
+        // The sensor no longer returns the rest of a partially read result
+        if self.started == StartedMeasurement::Remaining {
+            self.started = StartedMeasurement::None;
         }
+        Ok(())
     }
 
     /// Write an I²C command to the sensor.
+    ///
+    /// Nothing is sent if the command can't be sent in the current state,
+    /// see [`check_command`](Self::check_command).
     fn send_command(&mut self, command: Command) -> Result<(), Error<I2C::Error>> {
+        self.prepare_command(command)?;
+
         self.i2c
             .write(self.address, &command.as_bytes())
-            .map_err(Error::I2c)
+            .map_err(|source| Error::I2c {
+                op: command.operation(),
+                source,
+            })
     }
 
     /// Iterate over the provided buffer and validate the CRC8 checksum.
     ///
-    /// If the checksum is wrong, return `Error::Crc`.
+    /// If the checksum is wrong, return `Error::Crc` with the index of the
+    /// first 3-byte word that failed validation.
     ///
     /// Note: This method will consider every third byte a checksum byte. If
     /// the buffer size is not a multiple of 3, then not all data will be
     /// validated.
     fn validate_crc(&self, buf: &[u8]) -> Result<(), Error<I2C::Error>> {
-        let mut chunks = buf.chunks_exact(3);
-
-        for chunk in chunks.by_ref() {
-            if crc8(&chunk[..2]) != chunk[2] {
-                return Err(Error::Crc);
-            }
-        }
-
-        #[cfg(feature = "defmt")]
-        if !chunks.remainder().is_empty() {
-            defmt::warn!("Remaining data in buffer was not CRC8 validated");
-        }
-
-        Ok(())
+        Ok(inner::validate_crc(&self.crc_params, buf)?)
     }
 
     /// Read data into the provided buffer and validate the CRC8 checksum.
     ///
-    /// If the checksum is wrong, return `Error::Crc`.
+    /// If the checksum is wrong, return `Error::Crc`. No validation is done
+    /// when the driver is in [`CrcMode::Skip`].
     ///
     /// Note: This method will consider every third byte a checksum byte. If
     /// the buffer size is not a multiple of 3, then not all data will be
     /// validated.
     fn read_with_crc(&mut self, buf: &mut [u8]) -> Result<(), Error<I2C::Error>> {
-        self.i2c.read(self.address, buf)?;
-        self.validate_crc(buf)
+        self.read_without_crc(buf)?;
+        self.check_crc(buf)
     }
 
-    /// Return the raw ID register.
-    pub fn raw_id_register(&mut self) -> Result<u16, Error<I2C::Error>> {
-        // Request serial number
-        self.send_command(Command::ReadIdRegister)?;
+    /// Read data into the provided buffer without validating the CRC8
+    /// checksum, regardless of the [`CrcMode`].
+    ///
+    /// The checksum bytes are still read into the buffer, so every third
+    /// byte can be validated later. Corrupted data on the bus is not
+    /// detected, so only use this on short, well-shielded buses where the
+    /// cost of validation matters. To skip validation for every read of the
+    /// driver instead, use [`CrcMode::Skip`].
+    pub fn read_without_crc(&mut self, buf: &mut [u8]) -> Result<(), Error<I2C::Error>> {
+        self.i2c
+            .read(self.address, buf)
+            .map_err(|source| Error::I2c {
+                op: Operation::ReadResult,
+                source,
+            })
+    }
+
+    /// Send a command and read its response into the provided buffer,
+    /// validating the CRC8 checksum.
+    ///
+    /// Depending on the [`TransactionMode`], this is either done as a write
+    /// followed by a separate read, or a single `write_read` transaction.
+    fn command_read(&mut self, command: Command, buf: &mut [u8]) -> Result<(), Error<I2C::Error>> {
+        let result = match self.transaction_mode {
+            TransactionMode::Split => {
+                self.send_command(command)?;
+                self.i2c.read(self.address, buf)
+            }
+            TransactionMode::Combined => {
+                self.prepare_command(command)?;
+                self.i2c.write_read(self.address, &command.as_bytes(), buf)
+            }
+        };
+        result.map_err(|source| Error::I2c {
+            op: command.read_operation(),
+            source,
+        })?;
+        self.check_crc(buf)
+    }
+
+    /// The CRC8 parameters to decode frames with, or `None` when validation
+    /// is skipped.
+    fn frame_crc(&self) -> Option<Crc8Params> {
+        match self.crc_mode {
+            CrcMode::Skip => None,
+            CrcMode::Strict | CrcMode::StrictLength => Some(self.crc_params),
+        }
+    }
 
-        // Read id register
+    /// Validate the CRC8 checksum of the buffer according to the [`CrcMode`].
+    fn check_crc(&self, buf: &[u8]) -> Result<(), Error<I2C::Error>> {
+        Ok(inner::check_crc(self.crc_mode, &self.crc_params, buf)?)
+    }
+
+    /// Return the raw ID register.
+    pub fn raw_id_register(&mut self) -> Result<u16, Error<I2C::Error>> {
+        // Request serial number and read id register
         let mut buf = [0; 3];
-        self.read_with_crc(&mut buf)?;
+        self.command_read(Command::ReadIdRegister, &mut buf)?;
 
         Ok(u16::from_be_bytes([buf[0], buf[1]]))
     }
 
+    /// Return the decoded ID register.
+    pub fn id_register(&mut self) -> Result<IdRegister, Error<I2C::Error>> {
+        Ok(IdRegister::new(self.raw_id_register()?, self.kind))
+    }
+
     /// Return the 7-bit device identifier.
     ///
-    /// Should be 0x47 (71) for the SHTC3.
+    /// Should be 0x47 (71) for the SHTC3, and 0x07 for the SHTC1 and SHTW2.
     pub fn device_identifier(&mut self) -> Result<u8, Error<I2C::Error>> {
-        let ident = self.raw_id_register()?;
-        let lsb = (ident & 0b0011_1111) as u8;
-        let msb = ((ident & 0b0000_1000_0000_0000) >> 5) as u8;
-        Ok(lsb | msb)
+        Ok(self.id_register()?.identifier())
+    }
+
+    /// Read the device identifier and verify that it matches the sensor kind.
+    ///
+    /// Returns `Error::UnexpectedDevice` if the identifier is not the
+    /// expected one, such as 0x47 for the SHTC3.
+    pub fn check_id(&mut self) -> Result<(), Error<I2C::Error>> {
+        match self.device_identifier()? {
+            got if got == self.kind.identifier() => Ok(()),
+            got => Err(Error::UnexpectedDevice { got }),
+        }
+    }
+
+    /// Check that the sensor is present on the bus. (blocking)
+    ///
+    /// Wakes the sensor, verifies the device identifier and puts the sensor
+    /// back to sleep. The sleep command is sent even if the identifier check
+    /// failed, so the sensor isn't left awake. Returns
+    /// `Error::UnexpectedDevice` if the identifier is not the expected one.
+    ///
+    /// Sensors without a sleep mode only have their identifier verified.
+    pub fn probe(&mut self, delay: &mut impl DelayNs) -> Result<(), Error<I2C::Error>> {
+        if !self.kind.supports_sleep() {
+            return self.check_id();
+        }
+
+        self.wakeup(delay)?;
+        let result = self.check_id();
+        let sleep = self.sleep();
+        result.and(sleep)
     }
 
     /// Trigger a soft reset. (blocking)
@@ -362,7 +1214,7 @@ where
     /// reset command can be sent. This triggers the sensor to reset all
     /// internal state machines and reload calibration data from the memory.
     pub fn reset(&mut self, delay: &mut impl DelayNs) -> Result<(), Error<I2C::Error>> {
-        self.send_command(Command::SoftwareReset)?;
+        self.start_reset()?;
         // Table 5: 180-240 µs
         delay.delay_us(self.reset_duration());
         Ok(())
@@ -376,27 +1228,64 @@ where
     /// reset command can be sent. This triggers the sensor to reset all
     /// internal state machines and reload calibration data from the memory.
     pub fn start_reset(&mut self) -> Result<(), Error<I2C::Error>> {
-        self.send_command(Command::SoftwareReset)
+        self.send_command(Command::SoftwareReset)?;
+        self.started = StartedMeasurement::None;
+        Ok(())
     }
 
     /// Returns the reset duration for the SHTC3 in microseconds
+    ///
+    /// This is the maximum soft reset time of 240 µs (datasheet table 5).
+    /// Earlier versions returned 240 000 µs, i.e. 240 ms, by mistake.
     #[inline(always)]
     pub const fn reset_duration(&self) -> u32 {
-        240_000
+        Self::RESET_DURATION_US
+    }
+
+    /// Returns the reset duration for the SHTC3.
+    ///
+    /// ```
+    /// # use embedded_hal::i2c::I2c;
+    /// # use para_shtc3::ShtC3;
+    /// use embassy_time::Timer;
+    ///
+    /// async fn reset<I2C: I2c>(sht: &mut ShtC3<I2C>) -> Result<(), para_shtc3::Error<I2C::Error>> {
+    ///     sht.start_reset()?;
+    ///     Timer::after_micros(sht.reset_duration_t().to_micros().into()).await;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "fugit")]
+    #[inline(always)]
+    pub const fn reset_duration_t(&self) -> fugit::MicrosDurationU32 {
+        fugit::MicrosDurationU32::micros(Self::RESET_DURATION_US)
     }
 
     /// Set sensor to sleep mode.
     ///
     /// When in sleep mode, the sensor consumes around 0.3-0.6 µA. It requires
     /// a dedicated [`wakeup`](#method.wakeup) command to enable further I2C
-    /// communication.
+    /// communication. Until then, other commands return `Error::Asleep`
+    /// without being sent.
     pub fn sleep(&mut self) -> Result<(), Error<I2C::Error>> {
-        self.send_command(Command::Sleep)
+        self.send_command(Command::Sleep)?;
+        self.started = StartedMeasurement::None;
+        self.asleep = true;
+        Ok(())
+    }
+
+    /// Whether the sensor was put into [sleep mode](#method.sleep) and not
+    /// woken up since.
+    #[inline]
+    pub const fn is_asleep(&self) -> bool {
+        self.asleep
     }
 
     /// Wake up sensor from [sleep mode](#method.sleep).
     pub fn start_wakeup(&mut self) -> Result<(), Error<I2C::Error>> {
-        self.send_command(Command::WakeUp)
+        self.send_command(Command::WakeUp)?;
+        self.asleep = false;
+        Ok(())
     }
 
     /// Wake up sensor from [sleep mode](#method.sleep) and wait until it is ready.
@@ -405,6 +1294,44 @@ where
         delay.delay_us(self.wakeup_duration());
         Ok(())
     }
+
+    /// Wake up sensor from [sleep mode](#method.sleep), polling every
+    /// `poll_interval_us` until the sensor acknowledges a command instead of
+    /// always waiting the maximum wakeup duration. (blocking)
+    ///
+    /// Returns `Error::WakeupTimeout` if the sensor still doesn't acknowledge
+    /// after the maximum wakeup duration.
+    pub fn wakeup_polled(
+        &mut self,
+        delay: &mut impl DelayNs,
+        poll_interval_us: u32,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.start_wakeup()?;
+
+        let poll_interval_us = poll_interval_us.max(1);
+        let attempts = self.wakeup_duration().div_ceil(poll_interval_us);
+
+        for _ in 0..attempts {
+            delay.delay_us(poll_interval_us);
+            // Reading the ID register is harmless, and only acknowledged
+            // once the sensor is awake.
+            match self
+                .i2c
+                .write(self.address, &Command::ReadIdRegister.as_bytes())
+            {
+                Ok(()) => return Ok(()),
+                Err(e) if matches!(i2c::Error::kind(&e), i2c::ErrorKind::NoAcknowledge(_)) => {}
+                Err(source) => {
+                    return Err(Error::I2c {
+                        op: Operation::WakeUp,
+                        source,
+                    });
+                }
+            }
+        }
+
+        Err(Error::WakeupTimeout)
+    }
 }
 
 /// Non-blocking functions for starting / reading measurements.
@@ -421,14 +1348,70 @@ where
         &mut self,
         power_mode: PowerMode,
         order: MeasurementOrder,
+        started: StartedMeasurement,
     ) -> Result<(), Error<I2C::Error>> {
         // Request measurement
-        self.send_command(Command::Measure { power_mode, order })
+        self.send_command(Command::Measure { power_mode, order })?;
+        self.mode = power_mode;
+        self.order = order;
+        self.started = started;
+        Ok(())
+    }
+
+    /// Mark the started measurement as read, returning
+    /// `Error::MeasurementNotStarted` if no measurement was started, or if
+    /// both values are read after a partial start.
+    fn take_started(&mut self, combined: bool) -> Result<(), Error<I2C::Error>> {
+        match core::mem::take(&mut self.started) {
+            StartedMeasurement::None => Err(Error::MeasurementNotStarted),
+            StartedMeasurement::Partial if combined => Err(Error::MeasurementNotStarted),
+            StartedMeasurement::Partial | StartedMeasurement::Combined => Ok(()),
+            StartedMeasurement::Remaining => Err(Error::MeasurementNotStarted),
+        }
+    }
+
+    /// Start a measurement with the specified measurement order.
+    ///
+    /// The order determines which value is returned by
+    /// [`get_raw_partial_measurement_result`](crate::ShtC3::get_raw_partial_measurement_result()):
+    /// with [`MeasurementOrder::TemperatureFirst`] the partial result is the
+    /// temperature, with [`MeasurementOrder::HumidityFirst`] it is the
+    /// humidity.
+    pub fn start_measurement_ordered(
+        &mut self,
+        mode: PowerMode,
+        order: MeasurementOrder,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.start_measure_partial(mode, order, StartedMeasurement::Combined)
     }
 
     /// Start a combined temperature / humidity measurement.
     pub fn start_measurement(&mut self, mode: PowerMode) -> Result<(), Error<I2C::Error>> {
-        self.start_measure_partial(mode, MeasurementOrder::TemperatureFirst)
+        self.start_measure_partial(
+            mode,
+            MeasurementOrder::TemperatureFirst,
+            StartedMeasurement::Combined,
+        )
+    }
+
+    /// Start a combined temperature / humidity measurement, returning a
+    /// [`Pending`] token that tracks when the result is ready.
+    ///
+    /// `now_us` is the current time in microseconds on the caller's clock.
+    /// The result is read with
+    /// [`get_pending_measurement_result`](Self::get_pending_measurement_result).
+    pub fn start_measurement_timed(
+        &mut self,
+        mode: PowerMode,
+        now_us: u64,
+    ) -> Result<Pending, Error<I2C::Error>> {
+        self.start_measurement(mode)?;
+        Ok(Pending::new(
+            mode,
+            self.order,
+            now_us,
+            self.max_measurement_duration(mode),
+        ))
     }
 
     /// Start a temperature measurement.
@@ -436,49 +1419,215 @@ where
         &mut self,
         mode: PowerMode,
     ) -> Result<(), Error<I2C::Error>> {
-        self.start_measure_partial(mode, MeasurementOrder::TemperatureFirst)
+        self.start_measure_partial(
+            mode,
+            MeasurementOrder::TemperatureFirst,
+            StartedMeasurement::Partial,
+        )
     }
 
     /// Start a humidity measurement.
     pub fn start_humidity_measurement(&mut self, mode: PowerMode) -> Result<(), Error<I2C::Error>> {
-        self.start_measure_partial(mode, MeasurementOrder::HumidityFirst)
+        self.start_measure_partial(
+            mode,
+            MeasurementOrder::HumidityFirst,
+            StartedMeasurement::Partial,
+        )
+    }
+
+    /// Check whether the started measurement is finished, without reading its
+    /// result.
+    ///
+    /// This sends an address-only write, which the sensor doesn't acknowledge
+    /// while it is still measuring. No result data is consumed, so the
+    /// result is read as usual afterwards, e.g. with
+    /// [`get_measurement_result`](Self::get_measurement_result). The I²C bus
+    /// implementation has to support empty writes for this.
+    ///
+    /// Returns `Error::MeasurementNotStarted` if no measurement was started
+    /// since the last read. Bus errors other than a missing acknowledge are
+    /// returned as an error.
+    pub fn is_measurement_ready(&mut self) -> Result<bool, Error<I2C::Error>> {
+        if matches!(
+            self.started,
+            StartedMeasurement::None | StartedMeasurement::Remaining
+        ) {
+            return Err(Error::MeasurementNotStarted);
+        }
+
+        match self.i2c.write(self.address, &[]) {
+            Ok(()) => Ok(true),
+            Err(e) if matches!(i2c::Error::kind(&e), i2c::ErrorKind::NoAcknowledge(_)) => Ok(false),
+            Err(source) => Err(Error::I2c {
+                op: Operation::ReadResult,
+                source,
+            }),
+        }
     }
 
     /// Read the result of a temperature / humidity measurement.
+    ///
+    /// The response is decoded according to the order of the last started
+    /// measurement. Returns `Error::MeasurementNotStarted` unless a combined
+    /// measurement was started since the last read.
     pub fn get_measurement_result(&mut self) -> Result<Measurement, Error<I2C::Error>> {
         let raw = self.get_raw_measurement_result()?;
-        Ok(raw.into())
+        Ok(Measurement {
+            temperature: inner::calibrated_temperature(raw.temperature, self.temperature_offset),
+            humidity: inner::calibrated_humidity(raw.humidity, self.humidity_offset),
+        })
+    }
+
+    /// Read the result of the measurement started with
+    /// [`start_measurement_timed`](Self::start_measurement_timed).
+    ///
+    /// Returns `Error::MeasurementNotStarted` if a measurement with a
+    /// different power mode or order was started in the meantime.
+    pub fn get_pending_measurement_result(
+        &mut self,
+        pending: Pending,
+    ) -> Result<Measurement, Error<I2C::Error>> {
+        if pending.mode() != self.mode || pending.order != self.order {
+            return Err(Error::MeasurementNotStarted);
+        }
+
+        self.get_measurement_result()
+    }
+
+    /// Read the result of a temperature / humidity measurement, keeping
+    /// whichever half passed CRC validation.
+    ///
+    /// A half that failed validation is returned as `None`, while bus errors
+    /// are still returned as an error.
+    pub fn get_measurement_result_lossy(
+        &mut self,
+    ) -> Result<PartialMeasurement, Error<I2C::Error>> {
+        self.take_started(true)?;
+        let mut buf = [0; 6];
+        self.read_without_crc(&mut buf)?;
+
+        let is_valid =
+            |word: &[u8]| self.crc_mode == CrcMode::Skip || self.validate_crc(word).is_ok();
+
+        let (temperature, humidity) = match self.order {
+            MeasurementOrder::TemperatureFirst => buf.split_at(3),
+            MeasurementOrder::HumidityFirst => {
+                let (humidity, temperature) = buf.split_at(3);
+                (temperature, humidity)
+            }
+        };
+
+        Ok(PartialMeasurement {
+            temperature: is_valid(temperature).then(|| {
+                inner::calibrated_temperature(
+                    u16::from_be_bytes([temperature[0], temperature[1]]),
+                    self.temperature_offset,
+                )
+            }),
+            humidity: is_valid(humidity).then(|| {
+                inner::calibrated_humidity(
+                    u16::from_be_bytes([humidity[0], humidity[1]]),
+                    self.humidity_offset,
+                )
+            }),
+        })
     }
 
     /// Read the result of a temperature measurement.
     pub fn get_temperature_measurement_result(&mut self) -> Result<Temperature, Error<I2C::Error>> {
         let raw = self.get_raw_partial_measurement_result()?;
-        Ok(Temperature::from_raw(raw))
+        Ok(inner::calibrated_temperature(raw, self.temperature_offset))
     }
 
     /// Read the result of a humidity measurement.
     pub fn get_humidity_measurement_result(&mut self) -> Result<Humidity, Error<I2C::Error>> {
         let raw = self.get_raw_partial_measurement_result()?;
-        Ok(Humidity::from_raw(raw))
+        Ok(inner::calibrated_humidity(raw, self.humidity_offset))
     }
 
     /// Read the raw result of a combined temperature / humidity measurement.
+    ///
+    /// The response is decoded according to the order of the last started
+    /// measurement.
     pub fn get_raw_measurement_result(&mut self) -> Result<RawMeasurement, Error<I2C::Error>> {
+        self.take_started(true)?;
+        let mut buf = [0; 6];
+        self.read_without_crc(&mut buf)?;
+        Ok(frame::decode_measurement_frame_with(
+            &buf,
+            self.order,
+            self.frame_crc(),
+        )?)
+    }
+
+    /// Read the raw 6-byte result of a combined temperature / humidity
+    /// measurement, including the checksum bytes.
+    ///
+    /// The buffer is CRC validated before being returned. Bytes 0-1 and 3-4
+    /// are the measurement words, while bytes 2 and 5 are their respective
+    /// CRC8 checksums. The words are returned as sent by the sensor, so their
+    /// order depends on the [`MeasurementOrder`] of the started measurement.
+    pub fn get_raw_measurement_bytes(&mut self) -> Result<[u8; 6], Error<I2C::Error>> {
+        self.take_started(true)?;
         let mut buf = [0; 6];
         self.read_with_crc(&mut buf)?;
-        Ok(RawMeasurement {
-            temperature: u16::from_be_bytes([buf[0], buf[1]]),
-            humidity: u16::from_be_bytes([buf[3], buf[4]]),
-        })
+        Ok(buf)
     }
 
     /// Read the raw result of a partial temperature or humidity measurement.
     ///
-    /// Return the raw 3-byte buffer (after validating CRC).
+    /// Return the raw 3-byte buffer (after validating CRC). Returns
+    /// `Error::MeasurementNotStarted` unless a measurement was started since
+    /// the last read.
     pub fn get_raw_partial_measurement_result(&mut self) -> Result<u16, Error<I2C::Error>> {
+        self.take_started(false)?;
         let mut buf = [0; 3];
-        self.read_with_crc(&mut buf)?;
-        Ok(u16::from_be_bytes([buf[0], buf[1]]))
+        self.read_without_crc(&mut buf)?;
+        let raw = frame::decode_partial_frame_with(&buf, self.frame_crc())?;
+        self.started = StartedMeasurement::Remaining;
+        Ok(raw)
+    }
+
+    /// Read the raw second value of a measurement after its first value was
+    /// read by
+    /// [`get_raw_partial_measurement_result`](Self::get_raw_partial_measurement_result).
+    ///
+    /// This continues the measurement frame with another 3-byte read, so it
+    /// only works before any new command is sent or the sensor is put to
+    /// sleep. Otherwise `Error::MeasurementNotStarted` is returned.
+    pub fn get_remaining_partial_result(&mut self) -> Result<u16, Error<I2C::Error>> {
+        if core::mem::take(&mut self.started) != StartedMeasurement::Remaining {
+            return Err(Error::MeasurementNotStarted);
+        }
+        let mut buf = [0; 3];
+        self.read_without_crc(&mut buf)?;
+        Ok(frame::decode_partial_frame_with(&buf, self.frame_crc())?)
+    }
+
+    /// Read the remaining temperature of a humidity first measurement, see
+    /// [`get_remaining_partial_result`](Self::get_remaining_partial_result).
+    ///
+    /// Returns `Error::MeasurementNotStarted` if the temperature was already
+    /// read first.
+    pub fn get_remaining_temperature_result(&mut self) -> Result<Temperature, Error<I2C::Error>> {
+        if self.order != MeasurementOrder::HumidityFirst {
+            return Err(Error::MeasurementNotStarted);
+        }
+        let raw = self.get_remaining_partial_result()?;
+        Ok(inner::calibrated_temperature(raw, self.temperature_offset))
+    }
+
+    /// Read the remaining humidity of a temperature first measurement, see
+    /// [`get_remaining_partial_result`](Self::get_remaining_partial_result).
+    ///
+    /// Returns `Error::MeasurementNotStarted` if the humidity was already read
+    /// first.
+    pub fn get_remaining_humidity_result(&mut self) -> Result<Humidity, Error<I2C::Error>> {
+        if self.order != MeasurementOrder::TemperatureFirst {
+            return Err(Error::MeasurementNotStarted);
+        }
+        let raw = self.get_remaining_partial_result()?;
+        Ok(inner::calibrated_humidity(raw, self.humidity_offset))
     }
 }
 
@@ -487,11 +1636,58 @@ impl<I2C> ShtC3<I2C>
 where
     I2C: I2c<SevenBitAddress>,
 {
+    /// Wake up the sensor and return a guard that puts it back to sleep when
+    /// finished or dropped. (blocking)
+    ///
+    /// Sensors without a sleep mode are neither woken up nor put to sleep.
+    pub fn awake_session(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<AwakeSession<'_, I2C>, Error<I2C::Error>> {
+        AwakeSession::new(self, delay)
+    }
+
+    /// Run a boot-time self test of the sensor. (blocking)
+    ///
+    /// Wakes the sensor (if it supports sleep mode), verifies the device
+    /// identifier and runs a normal mode measurement. Returns
+    /// `Error::ImplausibleReading` if the measured values are outside of the
+    /// sensor's operating range (-40 to 125 °C, 0 to 100 %RH), see
+    /// [`Measurement::is_plausible`].
+    pub fn self_test(
+        &mut self,
+        delay: &mut impl DelayNs,
+    ) -> Result<Measurement, Error<I2C::Error>> {
+        if self.kind.supports_sleep() {
+            self.wakeup(delay)?;
+        }
+        self.check_id()?;
+
+        let measurement = self.measure(PowerMode::NormalMode, delay)?;
+
+        if measurement.is_plausible() {
+            Ok(measurement)
+        } else {
+            Err(Error::ImplausibleReading(measurement))
+        }
+    }
+
     /// Wait the maximum time needed for the given measurement mode
     pub fn wait_for_measurement(&mut self, mode: PowerMode, delay: &mut impl DelayNs) {
         delay.delay_us(self.max_measurement_duration(mode));
     }
 
+    /// Wait the maximum time needed for the given measurement mode, extended
+    /// by a safety margin given in percent.
+    pub fn wait_for_measurement_with_margin(
+        &mut self,
+        mode: PowerMode,
+        margin_percent: u32,
+        delay: &mut impl DelayNs,
+    ) {
+        delay.delay_us(self.max_measurement_duration_with_margin(mode, margin_percent));
+    }
+
     /// Run a temperature/humidity measurement and return the combined result.
     ///
     /// This is a blocking function call.
@@ -505,6 +1701,225 @@ where
         self.get_measurement_result()
     }
 
+    /// Wake up the sensor, run a temperature/humidity measurement and put the
+    /// sensor back to sleep. (blocking)
+    ///
+    /// The sleep command is sent even if the measurement failed, and the
+    /// first error is returned. Sensors without a sleep mode are neither
+    /// woken up nor put to sleep.
+    pub fn measure_with_sleep(
+        &mut self,
+        mode: PowerMode,
+        delay: &mut impl DelayNs,
+    ) -> Result<Measurement, Error<I2C::Error>> {
+        let mut session = self.awake_session(delay)?;
+        let result = session.measure(mode, delay);
+        let slept = session.finish();
+
+        let measurement = result?;
+        slept?;
+        Ok(measurement)
+    }
+
+    /// Run a temperature/humidity measurement and return the combined result,
+    /// reading it as soon as the sensor finished the measurement. (blocking)
+    ///
+    /// Waits the typical measurement duration, then polls for the result
+    /// every `poll_step_us` while the sensor doesn't acknowledge the read, up
+    /// to the maximum measurement duration. Returns `Error::Timeout` if the
+    /// result still isn't available by then.
+    pub fn measure_adaptive(
+        &mut self,
+        mode: PowerMode,
+        delay: &mut impl DelayNs,
+        poll_step_us: u32,
+    ) -> Result<Measurement, Error<I2C::Error>> {
+        let max_duration = self.max_measurement_duration(mode);
+        let poll_step_us = poll_step_us.max(1);
+
+        self.start_measurement(mode)?;
+        let mut waited = self.typical_measurement_duration(mode);
+        delay.delay_us(waited);
+
+        loop {
+            match self.get_measurement_result() {
+                Err(Error::I2c { source, .. })
+                    if matches!(i2c::Error::kind(&source), i2c::ErrorKind::NoAcknowledge(_)) =>
+                {
+                    if waited >= max_duration {
+                        return Err(Error::Timeout);
+                    }
+                    // The measurement is still running, read it again
+                    self.started = StartedMeasurement::Combined;
+                    let step = poll_step_us.min(max_duration - waited);
+                    delay.delay_us(step);
+                    waited += step;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Run a temperature/humidity measurement and return the combined result,
+    /// restarting the measurement up to `max_retries` times if the response
+    /// fails CRC validation.
+    ///
+    /// I²C errors are returned immediately. Note that every retry adds a full
+    /// measurement to the worst-case latency.
+    ///
+    /// This is a blocking function call.
+    pub fn measure_with_retries(
+        &mut self,
+        mode: PowerMode,
+        delay: &mut impl DelayNs,
+        max_retries: u8,
+    ) -> Result<Measurement, Error<I2C::Error>> {
+        let mut retries = 0;
+        loop {
+            match self.measure(mode, delay) {
+                Err(Error::Crc { .. }) if retries < max_retries => retries += 1,
+                result => return result,
+            }
+        }
+    }
+
+    /// Run a temperature/humidity measurement and return the combined result,
+    /// waiting an additional safety margin (in percent) for the measurement
+    /// to complete.
+    ///
+    /// This is a blocking function call.
+    pub fn measure_with_margin(
+        &mut self,
+        mode: PowerMode,
+        margin_percent: u32,
+        delay: &mut impl DelayNs,
+    ) -> Result<Measurement, Error<I2C::Error>> {
+        self.start_measurement(mode)?;
+        self.wait_for_measurement_with_margin(mode, margin_percent, delay);
+        self.get_measurement_result()
+    }
+
+    /// Run a temperature/humidity measurement and return the combined result,
+    /// tagged with the settings it was taken with.
+    ///
+    /// This is a blocking function call.
+    pub fn measure_tagged(
+        &mut self,
+        mode: PowerMode,
+        delay: &mut impl DelayNs,
+    ) -> Result<(Measurement, MeasurementProvenance), Error<I2C::Error>> {
+        let measurement = self.measure(mode, delay)?;
+        let provenance = MeasurementProvenance {
+            power_mode: mode,
+            order: self.order,
+        };
+        Ok((measurement, provenance))
+    }
+
+    /// Return an endless iterator running a blocking temperature/humidity
+    /// measurement on every call to `next()`.
+    ///
+    /// Combine it with [`MeasurementsExt::moving_average`] for a smoothed
+    /// signal.
+    pub fn measurements<'a, D: DelayNs>(
+        &'a mut self,
+        mode: PowerMode,
+        delay: &'a mut D,
+    ) -> Measurements<'a, I2C, D> {
+        Measurements::new(self, mode, delay)
+    }
+
+    /// Run several temperature/humidity measurements and return their
+    /// average.
+    ///
+    /// Waits `inter_sample_delay_us` between consecutive measurements, and
+    /// aborts on the first bus or CRC error. A sample count of 0 is treated
+    /// as 1.
+    ///
+    /// This is a blocking function call.
+    pub fn measure_averaged(
+        &mut self,
+        mode: PowerMode,
+        samples: u8,
+        inter_sample_delay_us: u32,
+        delay: &mut impl DelayNs,
+    ) -> Result<Measurement, Error<I2C::Error>> {
+        let mut acc = MeasurementAccumulator::new();
+
+        for sample in 0..samples.max(1) {
+            if sample > 0 {
+                delay.delay_us(inter_sample_delay_us);
+            }
+            acc.add(self.measure(mode, delay)?);
+        }
+
+        Ok(acc.mean().unwrap_or_default())
+    }
+
+    /// Run temperature/humidity measurements as described by the
+    /// [`MeasureConfig`] and return their average.
+    ///
+    /// The CRC mode of the configuration only applies to these measurements,
+    /// the driver's own [`CrcMode`] is restored afterwards.
+    ///
+    /// This is a blocking function call.
+    pub fn measure_with(
+        &mut self,
+        config: &MeasureConfig,
+        delay: &mut impl DelayNs,
+    ) -> Result<Measurement, Error<I2C::Error>> {
+        let crc_mode = self.crc_mode;
+        self.crc_mode = config.crc_mode;
+
+        let mut acc = MeasurementAccumulator::new();
+        let result: Result<(), Error<I2C::Error>> = (0..config.samples.max(1)).try_for_each(|_| {
+            let measurement =
+                self.measure_with_margin(config.power_mode, config.margin_percent, delay)?;
+            acc.add(measurement);
+            Ok(())
+        });
+
+        self.crc_mode = crc_mode;
+        result?;
+        Ok(acc.mean().unwrap_or_default())
+    }
+
+    /// Run a temperature/humidity measurement in both measurement orders and
+    /// return the average of the two results.
+    ///
+    /// Returns `Error::Inconsistent` if the two temperatures differ by more
+    /// than `tolerance_mk` millidegrees, which catches corrupted readings
+    /// that happen to pass CRC validation.
+    ///
+    /// This is a blocking function call.
+    pub fn measure_validated(
+        &mut self,
+        mode: PowerMode,
+        delay: &mut impl DelayNs,
+        tolerance_mk: i32,
+    ) -> Result<Measurement, Error<I2C::Error>> {
+        self.start_measurement_ordered(mode, MeasurementOrder::TemperatureFirst)?;
+        self.wait_for_measurement(mode, delay);
+        let temperature_first = self.get_measurement_result()?;
+
+        self.start_measurement_ordered(mode, MeasurementOrder::HumidityFirst)?;
+        self.wait_for_measurement(mode, delay);
+        let humidity_first = self.get_measurement_result()?;
+
+        let delta = temperature_first.delta(&humidity_first);
+        if delta.temperature.abs().as_millidegrees() > tolerance_mk {
+            return Err(Error::Inconsistent {
+                temperature_first,
+                humidity_first,
+            });
+        }
+
+        let mut acc = MeasurementAccumulator::new();
+        acc.add(temperature_first);
+        acc.add(humidity_first);
+        Ok(acc.mean().unwrap_or(temperature_first))
+    }
+
     /// Run a temperature measurement and return the result.
     ///
     /// This is a blocking function call.
@@ -544,17 +1959,58 @@ mod tests {
 
     use super::*;
 
-    use embedded_hal::i2c::ErrorKind;
+    use crate::crc::crc8;
+    use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
     use embedded_hal_mock::eh1::{
         delay::NoopDelay,
         i2c::{Mock as I2cMock, Transaction},
     };
 
-    const SHT_ADDR: u8 = 0x70;
+    pub(crate) const SHT_ADDR: u8 = 0x70;
 
     mod core {
         use super::*;
 
+        /// Test validation with non-default CRC8 parameters.
+        #[test]
+        fn custom_crc_params() {
+            let params = Crc8Params {
+                poly: 0x31,
+                init: 0x00,
+            };
+            let word = [0xbe, 0xef, params.checksum(&[0xbe, 0xef])];
+            let expectations = [
+                Transaction::read(SHT_ADDR, word.to_vec()),
+                Transaction::read(SHT_ADDR, word.to_vec()),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new_with_crc_params(mock, params);
+            assert_eq!(sht.crc_params(), params);
+            let mut buf = [0; 3];
+            sht.read_with_crc(&mut buf).unwrap();
+
+            sht.set_crc_params(Crc8Params::default());
+            assert_eq!(
+                sht.read_with_crc(&mut buf),
+                Err(Error::Crc { word_index: 0 })
+            );
+            sht.destroy().done();
+        }
+
+        /// Test that `read_without_crc` ignores invalid checksums even in
+        /// strict mode.
+        #[test]
+        fn read_without_crc() {
+            let expectations = [Transaction::read(SHT_ADDR, alloc::vec![0xbe, 0xef, 0x00])];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            assert_eq!(sht.crc_mode(), CrcMode::Strict);
+            let mut buf = [0; 3];
+            sht.read_without_crc(&mut buf).unwrap();
+            assert_eq!(buf, [0xbe, 0xef, 0x00]);
+            sht.destroy().done();
+        }
+
         /// Test whether the `send_command` function propagates I²C errors.
         #[test]
         fn send_command_error() {
@@ -564,7 +2020,13 @@ mod tests {
             let mock = I2cMock::new(&expectations);
             let mut sht = ShtC3::new(mock);
             let err = sht.send_command(Command::ReadIdRegister).unwrap_err();
-            assert_eq!(err, Error::I2c(ErrorKind::Other));
+            assert_eq!(
+                err,
+                Error::I2c {
+                    op: Operation::ReadId,
+                    source: ErrorKind::Other
+                }
+            );
             sht.destroy().done();
         }
 
@@ -584,7 +2046,7 @@ mod tests {
 
             // Invalid CRC
             match sht.validate_crc(&[0xbe, 0xef, 0x91]) {
-                Err(Error::Crc) => {}
+                Err(Error::Crc { word_index: 0 }) => {}
                 Err(_) => panic!("Invalid error: Must be Crc"),
                 Ok(_) => panic!("CRC check did not fail"),
             }
@@ -595,7 +2057,7 @@ mod tests {
 
             // Invalid CRC (8 bytes)
             match sht.validate_crc(&[0xbe, 0xef, 0x92, 0xbe, 0xef, 0xff, 0x00, 0x00]) {
-                Err(Error::Crc) => {}
+                Err(Error::Crc { word_index: 1 }) => {}
                 Err(_) => panic!("Invalid error: Must be Crc"),
                 Ok(_) => panic!("CRC check did not fail"),
             }
@@ -603,31 +2065,108 @@ mod tests {
             sht.destroy().done();
         }
 
-        /// Test the `read_with_crc` function.
+        /// Test the `validate_crc_strict` function.
         #[test]
-        fn read_with_crc() {
-            let mut buf = [0; 3];
+        fn validate_crc_strict() {
+            use crate::inner::{CheckError, validate_crc_strict};
 
-            // Valid CRC
-            let expectations = [Transaction::read(SHT_ADDR, alloc::vec![0xbe, 0xef, 0x92])];
-            let mock = I2cMock::new(&expectations);
-            let mut sht = ShtC3::new(mock);
-            sht.read_with_crc(&mut buf).unwrap();
-            assert_eq!(buf, [0xbe, 0xef, 0x92]);
-            sht.destroy().done();
+            let params = Crc8Params::SENSIRION;
+
+            validate_crc_strict(&params, &[]).unwrap();
+            validate_crc_strict(&params, &[0xbe, 0xef, 0x92]).unwrap();
+            validate_crc_strict(&params, &[0xbe, 0xef, 0x92, 0xbe, 0xef, 0x92]).unwrap();
+
+            // Trailing bytes which are not a complete word
+            assert_eq!(
+                validate_crc_strict(&params, &[0xbe]),
+                Err(CheckError::InvalidLength)
+            );
+            assert_eq!(
+                validate_crc_strict(&params, &[0xbe, 0xef, 0x92, 0xbe, 0xef, 0x92, 0x00, 0x00]),
+                Err(CheckError::InvalidLength)
+            );
+
+            // Invalid CRC
+            assert_eq!(
+                validate_crc_strict(&params, &[0xbe, 0xef, 0x92, 0xbe, 0xef, 0xff]),
+                Err(CheckError::Crc { word_index: 1 })
+            );
+        }
+
+        /// Test the `read_with_crc` function in `StrictLength` mode.
+        #[test]
+        fn read_with_crc_strict_length() {
+            let mut buf = [0; 4];
+            let expectations = [Transaction::read(
+                SHT_ADDR,
+                alloc::vec![0xbe, 0xef, 0x92, 0x00],
+            )];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new_with_crc_mode(mock, CrcMode::StrictLength);
+            assert_eq!(sht.read_with_crc(&mut buf), Err(Error::InvalidLength));
+            sht.destroy().done();
+        }
+
+        /// Test the `read_with_crc` function.
+        #[test]
+        fn read_with_crc() {
+            let mut buf = [0; 3];
+
+            // Valid CRC
+            let expectations = [Transaction::read(SHT_ADDR, alloc::vec![0xbe, 0xef, 0x92])];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            sht.read_with_crc(&mut buf).unwrap();
+            assert_eq!(buf, [0xbe, 0xef, 0x92]);
+            sht.destroy().done();
 
             // Invalid CRC
             let expectations = [Transaction::read(SHT_ADDR, alloc::vec![0xbe, 0xef, 0x00])];
             let mock = I2cMock::new(&expectations);
             let mut sht = ShtC3::new(mock);
             match sht.read_with_crc(&mut buf) {
-                Err(Error::Crc) => {}
+                Err(Error::Crc { word_index: 0 }) => {}
                 Err(_) => panic!("Invalid error: Must be Crc"),
                 Ok(_) => panic!("CRC check did not fail"),
             }
             assert_eq!(buf, [0xbe, 0xef, 0x00]); // Buf was changed
             sht.destroy().done();
         }
+
+        /// Test the `read_with_crc` function with a corrupted checksum in
+        /// both CRC modes.
+        #[test]
+        fn read_with_crc_mode() {
+            let mut buf = [0; 6];
+            let data = alloc::vec![0xbe, 0xef, 0x92, 0xbe, 0xef, 0x00];
+
+            // Strict mode
+            let expectations = [Transaction::read(SHT_ADDR, data.clone())];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            assert_eq!(sht.crc_mode(), CrcMode::Strict);
+            assert_eq!(
+                sht.read_with_crc(&mut buf),
+                Err(Error::Crc { word_index: 1 })
+            );
+            sht.destroy().done();
+
+            // Skip mode, set on construction
+            let expectations = [Transaction::read(SHT_ADDR, data.clone())];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new_with_crc_mode(mock, CrcMode::Skip);
+            sht.read_with_crc(&mut buf).unwrap();
+            assert_eq!(buf, [0xbe, 0xef, 0x92, 0xbe, 0xef, 0x00]);
+            sht.destroy().done();
+
+            // Skip mode, set at runtime
+            let expectations = [Transaction::read(SHT_ADDR, data)];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            sht.set_crc_mode(CrcMode::Skip);
+            sht.read_with_crc(&mut buf).unwrap();
+            sht.destroy().done();
+        }
     }
 
     mod factory_functions {
@@ -637,14 +2176,97 @@ mod tests {
         fn new_shtc3() {
             let mock = I2cMock::new(&[]);
             let sht = ShtC3::new(mock);
-            assert_eq!(sht.address, 0x70);
+            assert_eq!(sht.address, SHT_ADDR);
+            sht.destroy().done();
+        }
+
+        #[test]
+        fn new_with_address() {
+            let address = 0x44;
+            let expectations = [Transaction::write(address, alloc::vec![0xB0, 0x98])];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new_with_address(mock, address);
+            assert_eq!(sht.address(), address);
+            sht.sleep().unwrap();
+            let (mut mock, state) = sht.into_parts();
+            assert_eq!(state.address, address);
+            mock.done();
+        }
+
+        /// Test that the driver state survives rebuilding the driver over a
+        /// new bus.
+        #[test]
+        fn from_parts() {
+            let mut sht = ShtC3::new_with_crc_mode(I2cMock::new(&[]), CrcMode::Skip);
+            sht.set_calibration(-1_500, 2_000);
+            sht.set_transaction_mode(TransactionMode::Combined);
+
+            let (mut mock, state) = sht.into_parts();
+            mock.done();
+            assert_eq!(state.temperature_offset, -1_500);
+            assert_eq!(state.humidity_offset, 2_000);
+            assert!(!state.asleep);
+
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+                Transaction::write(SHT_ADDR, alloc::vec![0xB0, 0x98]),
+            ];
+            let mut sht = ShtC3::from_parts(I2cMock::new(&expectations), state);
+            assert_eq!(sht.address(), SHT_ADDR);
+            assert_eq!(sht.crc_mode(), CrcMode::Skip);
+            assert_eq!(sht.transaction_mode(), TransactionMode::Combined);
+            assert_eq!(sht.calibration(), (-1_500, 2_000));
+
+            sht.start_measurement(PowerMode::LowPower).unwrap();
+            let measurement = sht.get_measurement_result().unwrap();
+            assert_eq!(measurement.temperature.as_millidegrees_celsius(), 22_230);
+            assert_eq!(measurement.humidity.as_millipercent(), 64_968);
+            sht.sleep().unwrap();
+
+            // The sleep tracking survives as well
+            let (mut mock, state) = sht.into_parts();
+            mock.done();
+            assert!(state.asleep);
+            let mut sht = ShtC3::from_parts(I2cMock::new(&[]), state);
+            assert_eq!(
+                sht.start_measurement(PowerMode::LowPower),
+                Err(Error::Asleep)
+            );
             sht.destroy().done();
         }
+
+        #[test]
+        #[should_panic(expected = "Invalid 7-bit I²C address")]
+        fn new_with_invalid_address() {
+            let _ = ShtC3::new_with_address(I2cMock::new(&[]), 0x80);
+        }
     }
 
     mod device_info {
         use super::*;
 
+        /// Test the identifier checks for other sensor kinds.
+        #[test]
+        fn check_id_kinds() {
+            // SHTC1 / SHTW2 do not define bit 11 of the ID register
+            let msb = 0b00001000;
+            let lsb = 0b00000111;
+            let crc = crc8(&[msb, lsb]);
+            for kind in [SensorKind::Shtc1, SensorKind::Shtw2] {
+                let expectations = [
+                    Transaction::write(SHT_ADDR, alloc::vec![0xef, 0xc8]),
+                    Transaction::read(SHT_ADDR, alloc::vec![msb, lsb, crc]),
+                ];
+                let mock = I2cMock::new(&expectations);
+                let mut sht = ShtC3::with_kind(mock, kind);
+                assert_eq!(sht.kind(), kind);
+                assert_eq!(sht.address(), SHT_ADDR);
+                sht.check_id().unwrap();
+                sht.destroy().done();
+            }
+        }
+
         /// Test the `raw_id_register` function.
         #[test]
         fn raw_id_register() {
@@ -662,6 +2284,26 @@ mod tests {
             sht.destroy().done();
         }
 
+        /// Test the `raw_id_register` function with combined transactions.
+        #[test]
+        fn raw_id_register_combined() {
+            let msb = 0b00001000;
+            let lsb = 0b00000111;
+            let crc = crc8(&[msb, lsb]);
+            let expectations = [Transaction::write_read(
+                SHT_ADDR,
+                alloc::vec![0xef, 0xc8],
+                alloc::vec![msb, lsb, crc],
+            )];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            assert_eq!(sht.transaction_mode(), TransactionMode::Split);
+            sht.set_transaction_mode(TransactionMode::Combined);
+            let val = sht.raw_id_register().unwrap();
+            assert_eq!(val, (msb as u16) << 8 | (lsb as u16));
+            sht.destroy().done();
+        }
+
         /// Test the `device_identifier` function.
         #[test]
         fn device_identifier() {
@@ -678,6 +2320,135 @@ mod tests {
             assert_eq!(ident, 0b01000111);
             sht.destroy().done();
         }
+
+        /// Test decoding the fields of synthetic ID register values.
+        #[test]
+        fn id_register_fields() {
+            let plain = IdRegister::new(0b0000_1000_0000_0111, SensorKind::Shtc3);
+            assert_eq!(plain.identifier(), 0x47);
+            assert_eq!(plain.reserved_upper(), 0);
+            assert_eq!(plain.reserved_middle(), 0);
+            assert!(plain.is_shtc3());
+
+            // Internal bits set don't change the identifier
+            let revision = IdRegister::new(0b1010_1101_0100_0111, SensorKind::Shtc3);
+            assert_eq!(revision.raw(), 0xAD47);
+            assert_eq!(revision.identifier(), 0x47);
+            assert_eq!(revision.reserved_upper(), 0b1010);
+            assert_eq!(revision.reserved_middle(), 0b10101);
+            assert!(revision.is_shtc3());
+
+            // The SHTC1 ignores bit 11, but is_shtc3 still checks it
+            let shtc1 = IdRegister::new(0b0000_1000_0000_0111, SensorKind::Shtc1);
+            assert_eq!(shtc1.identifier(), 0x07);
+            assert!(shtc1.is_shtc3());
+            let shtc1 = IdRegister::new(0b0000_0000_0000_0111, SensorKind::Shtc1);
+            assert_eq!(shtc1.identifier(), 0x07);
+            assert!(!shtc1.is_shtc3());
+        }
+
+        /// Test the `id_register` function.
+        #[test]
+        fn id_register() {
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0xef, 0xc8]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x08, 0x47, 0x1c]),
+            ];
+            let mut sht = ShtC3::new(I2cMock::new(&expectations));
+            let id = sht.id_register().unwrap();
+            assert_eq!(id.raw(), 0x0847);
+            assert_eq!(id.identifier(), 0x47);
+            assert_eq!(id.reserved_middle(), 0b00001);
+            sht.destroy().done();
+        }
+
+        /// Test the `check_id` function.
+        #[test]
+        fn check_id() {
+            let msb = 0b00001000;
+            let lsb = 0b00000111;
+            let crc = crc8(&[msb, lsb]);
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0xef, 0xc8]),
+                Transaction::read(SHT_ADDR, alloc::vec![msb, lsb, crc]),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            sht.check_id().unwrap();
+            sht.destroy().done();
+        }
+
+        /// Test the `check_id` function with a non SHTC3 identifier.
+        #[test]
+        fn check_id_unexpected_device() {
+            let msb = 0b00000000;
+            let lsb = 0b00000111;
+            let crc = crc8(&[msb, lsb]);
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0xef, 0xc8]),
+                Transaction::read(SHT_ADDR, alloc::vec![msb, lsb, crc]),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            let err = sht.check_id().unwrap_err();
+            assert_eq!(err, Error::UnexpectedDevice { got: 0b00000111 });
+            sht.destroy().done();
+        }
+    }
+
+    mod probe {
+        use super::*;
+        use embedded_hal::i2c::NoAcknowledgeSource;
+
+        #[test]
+        fn probe_ok() {
+            let crc = crc8(&[0b00001000, 0b00000111]);
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17]),
+                Transaction::write(SHT_ADDR, alloc::vec![0xef, 0xc8]),
+                Transaction::read(SHT_ADDR, alloc::vec![0b00001000, 0b00000111, crc]),
+                Transaction::write(SHT_ADDR, alloc::vec![0xB0, 0x98]),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            sht.probe(&mut NoopDelay).unwrap();
+            sht.destroy().done();
+        }
+
+        #[test]
+        fn probe_wrong_id() {
+            let crc = crc8(&[0x00, 0x01]);
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17]),
+                Transaction::write(SHT_ADDR, alloc::vec![0xef, 0xc8]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x00, 0x01, crc]),
+                // Sensor is put back to sleep despite the failure
+                Transaction::write(SHT_ADDR, alloc::vec![0xB0, 0x98]),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            let err = sht.probe(&mut NoopDelay).unwrap_err();
+            assert_eq!(err, Error::UnexpectedDevice { got: 0x01 });
+            sht.destroy().done();
+        }
+
+        #[test]
+        fn probe_wakeup_nack() {
+            let nack = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+            let expectations =
+                [Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17]).with_error(nack)];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            let err = sht.probe(&mut NoopDelay).unwrap_err();
+            assert_eq!(
+                err,
+                Error::I2c {
+                    op: Operation::WakeUp,
+                    source: nack
+                }
+            );
+            sht.destroy().done();
+        }
     }
 
     mod measurements {
@@ -781,65 +2552,1246 @@ mod tests {
             sht.destroy().done();
         }
 
-        /// Ensure that I²C write errors are handled when measuring.
+        /// Test that the sensor is put back to sleep after measuring, even
+        /// when the measurement failed.
         #[test]
-        fn measure_write_error() {
-            let expectations =
-                [Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C])
-                    .with_error(ErrorKind::Other)];
+        fn measure_with_sleep() {
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17]),
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+                Transaction::write(SHT_ADDR, alloc::vec![0xB0, 0x98]),
+                // CRC failure
+                Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17]),
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0x00, 0xa1, 0x33, 0x1c]),
+                Transaction::write(SHT_ADDR, alloc::vec![0xB0, 0x98]),
+                // Failure to sleep after a successful measurement
+                Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17]),
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+                Transaction::write(SHT_ADDR, alloc::vec![0xB0, 0x98]).with_error(ErrorKind::Other),
+            ];
             let mock = I2cMock::new(&expectations);
             let mut sht = ShtC3::new(mock);
-            let err = sht
-                .measure(PowerMode::LowPower, &mut NoopDelay)
-                .unwrap_err();
-            assert_eq!(err, Error::I2c(ErrorKind::Other));
+            let mode = PowerMode::NormalMode;
+
+            let measurement = sht.measure_with_sleep(mode, &mut NoopDelay).unwrap();
+            assert_eq!(measurement.temperature.as_millidegrees_celsius(), 23_730);
+            assert!(sht.is_asleep());
+
+            assert_eq!(
+                sht.measure_with_sleep(mode, &mut NoopDelay),
+                Err(Error::Crc { word_index: 0 })
+            );
+            assert!(sht.is_asleep());
+
+            assert_eq!(
+                sht.measure_with_sleep(mode, &mut NoopDelay),
+                Err(Error::I2c {
+                    op: Operation::Sleep,
+                    source: ErrorKind::Other
+                })
+            );
+
             sht.destroy().done();
         }
-    }
 
-    mod power_management {
-        use super::*;
-
-        /// Test the `sleep` function.
+        /// Test that the adaptive measurement reads the result as soon as it
+        /// is available.
         #[test]
-        fn sleep() {
-            let expectations = [Transaction::write(SHT_ADDR, alloc::vec![0xB0, 0x98])];
+        fn measure_adaptive() {
+            let nack = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+            let bytes = alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c];
+            let expectations = [
+                // Ready on the first poll
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, bytes.clone()),
+                // Ready on the third poll
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, bytes.clone()).with_error(nack),
+                Transaction::read(SHT_ADDR, bytes.clone()).with_error(nack),
+                Transaction::read(SHT_ADDR, bytes.clone()),
+                // Not ready within 0.8 ms
+                Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+                Transaction::read(SHT_ADDR, bytes.clone()).with_error(nack),
+                Transaction::read(SHT_ADDR, bytes.clone()).with_error(nack),
+                Transaction::read(SHT_ADDR, bytes.clone()).with_error(nack),
+                // Other errors are returned right away
+                Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+                Transaction::read(SHT_ADDR, bytes).with_error(ErrorKind::Other),
+            ];
             let mock = I2cMock::new(&expectations);
             let mut sht = ShtC3::new(mock);
-            sht.sleep().unwrap();
+
+            let measurement = sht
+                .measure_adaptive(PowerMode::NormalMode, &mut NoopDelay, 500)
+                .unwrap();
+            assert_eq!(measurement.temperature.as_millidegrees_celsius(), 23_730);
+            let measurement = sht
+                .measure_adaptive(PowerMode::NormalMode, &mut NoopDelay, 500)
+                .unwrap();
+            assert_eq!(measurement.humidity.as_millipercent(), 62_968);
+
+            assert_eq!(
+                sht.measure_adaptive(PowerMode::LowPower, &mut NoopDelay, 50),
+                Err(Error::Timeout)
+            );
+            assert_eq!(
+                sht.measure_adaptive(PowerMode::LowPower, &mut NoopDelay, 50),
+                Err(Error::I2c {
+                    op: Operation::ReadResult,
+                    source: ErrorKind::Other
+                })
+            );
+
             sht.destroy().done();
         }
 
-        /// Test the `wakeup` function.
+        /// Test that the kind of bus errors is exposed.
         #[test]
-        fn wakeup() {
-            let expectations = [Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17])];
+        fn error_kind() {
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C])
+                    .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+                Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C])
+                    .with_error(ErrorKind::ArbitrationLoss),
+                Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1d]),
+            ];
+            let mut sht = ShtC3::new(I2cMock::new(&expectations));
+
+            let err = sht.start_measurement(PowerMode::LowPower).unwrap_err();
+            assert_eq!(
+                err.kind(),
+                Some(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))
+            );
+
+            let err = sht.start_measurement(PowerMode::LowPower).unwrap_err();
+            assert_eq!(err.kind(), Some(ErrorKind::ArbitrationLoss));
+
+            // Errors which aren't bus errors have no kind
+            sht.start_measurement(PowerMode::LowPower).unwrap();
+            let err = sht.get_measurement_result().unwrap_err();
+            assert_eq!(err, Error::Crc { word_index: 1 });
+            assert_eq!(err.kind(), None);
+
+            sht.destroy().done();
+        }
+
+        /// Test the `Display` output of errors.
+        #[test]
+        fn error_display() {
+            use alloc::string::ToString;
+
+            let err: Error<ErrorKind> = Error::I2c {
+                op: Operation::Measure,
+                source: ErrorKind::Bus,
+            };
+            assert!(
+                err.to_string()
+                    .starts_with("I2C error during Measure: Bus error")
+            );
+
+            let err: Error<ErrorKind> = Error::Crc { word_index: 1 };
+            assert_eq!(err.to_string(), "CRC mismatch in word 1");
+
+            let _: &dyn ::core::error::Error = &err;
+        }
+
+        /// Test polling the readiness of a measurement.
+        #[test]
+        fn is_measurement_ready() {
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+                Transaction::write(SHT_ADDR, alloc::vec![])
+                    .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+                Transaction::write(SHT_ADDR, alloc::vec![]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+                Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+                Transaction::write(SHT_ADDR, alloc::vec![]).with_error(ErrorKind::Bus),
+            ];
+            let mut sht = ShtC3::new(I2cMock::new(&expectations));
+
+            assert_eq!(
+                sht.is_measurement_ready(),
+                Err(Error::MeasurementNotStarted)
+            );
+
+            sht.start_measurement(PowerMode::LowPower).unwrap();
+            assert_eq!(sht.is_measurement_ready(), Ok(false));
+            assert_eq!(sht.is_measurement_ready(), Ok(true));
+            let measurement = sht.get_measurement_result().unwrap();
+            assert_eq!(measurement.temperature.as_millidegrees_celsius(), 23_730);
+
+            // Genuine bus errors are surfaced
+            sht.start_measurement(PowerMode::LowPower).unwrap();
+            assert_eq!(
+                sht.is_measurement_ready(),
+                Err(Error::I2c {
+                    op: Operation::ReadResult,
+                    source: ErrorKind::Bus
+                })
+            );
+
+            sht.destroy().done();
+        }
+
+        /// Test reading a measurement in two partial reads.
+        #[test]
+        fn remaining_partial_result() {
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7]),
+                Transaction::read(SHT_ADDR, alloc::vec![0xa1, 0x33, 0x1c]),
+                Transaction::write(SHT_ADDR, alloc::vec![0x58, 0xE0]),
+                Transaction::read(SHT_ADDR, alloc::vec![0xa1, 0x33, 0x1c]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7]),
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7]),
+                Transaction::write(SHT_ADDR, alloc::vec![0xef, 0xc8]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x08, 0x47, 0x1c]),
+            ];
             let mock = I2cMock::new(&expectations);
             let mut sht = ShtC3::new(mock);
-            sht.wakeup(&mut NoopDelay).unwrap();
+            let mode = PowerMode::NormalMode;
+
+            sht.start_measurement(mode).unwrap();
+            assert_eq!(
+                sht.get_temperature_measurement_result()
+                    .unwrap()
+                    .as_millidegrees_celsius(),
+                23_730
+            );
+            assert_eq!(
+                sht.get_remaining_temperature_result(),
+                Err(Error::MeasurementNotStarted)
+            );
+            assert_eq!(
+                sht.get_remaining_humidity_result()
+                    .unwrap()
+                    .as_millipercent(),
+                62_968
+            );
+            // The remainder can only be read once
+            assert_eq!(
+                sht.get_remaining_partial_result(),
+                Err(Error::MeasurementNotStarted)
+            );
+
+            sht.start_measurement_ordered(mode, MeasurementOrder::HumidityFirst)
+                .unwrap();
+            assert_eq!(sht.get_raw_partial_measurement_result().unwrap(), 0xa133);
+            assert_eq!(
+                sht.get_remaining_temperature_result()
+                    .unwrap()
+                    .as_millidegrees_celsius(),
+                23_730
+            );
+
+            // Sending a command discards the remainder
+            sht.start_measurement(mode).unwrap();
+            sht.get_raw_partial_measurement_result().unwrap();
+            sht.raw_id_register().unwrap();
+            assert_eq!(
+                sht.get_remaining_partial_result(),
+                Err(Error::MeasurementNotStarted)
+            );
+
             sht.destroy().done();
         }
 
-        /// Test the `reset` function.
+        /// Test that a combined command transaction discards the remainder of
+        /// a partially read measurement as well.
         #[test]
-        fn reset() {
-            let expectations = [Transaction::write(SHT_ADDR, alloc::vec![0x80, 0x5D])];
+        fn remaining_partial_result_combined() {
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7]),
+                Transaction::write_read(
+                    SHT_ADDR,
+                    alloc::vec![0xef, 0xc8],
+                    alloc::vec![0x08, 0x47, 0x1c],
+                ),
+            ];
+            let mut sht = ShtC3::new(I2cMock::new(&expectations));
+            sht.set_transaction_mode(TransactionMode::Combined);
+
+            sht.start_measurement(PowerMode::NormalMode).unwrap();
+            assert_eq!(sht.get_raw_partial_measurement_result().unwrap(), 0x648b);
+            sht.raw_id_register().unwrap();
+            assert_eq!(
+                sht.get_remaining_partial_result(),
+                Err(Error::MeasurementNotStarted)
+            );
+
+            sht.destroy().done();
+        }
+
+        /// Test that results can't be read without starting a matching
+        /// measurement, without doing any I/O.
+        #[test]
+        fn measurement_not_started() {
+            let bytes = alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c];
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, bytes.clone()),
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::write(SHT_ADDR, alloc::vec![0xB0, 0x98]),
+            ];
             let mock = I2cMock::new(&expectations);
             let mut sht = ShtC3::new(mock);
-            sht.reset(&mut NoopDelay).unwrap();
+            let mode = PowerMode::NormalMode;
+
+            assert_eq!(
+                sht.get_measurement_result(),
+                Err(Error::MeasurementNotStarted)
+            );
+            assert_eq!(
+                sht.get_temperature_measurement_result(),
+                Err(Error::MeasurementNotStarted)
+            );
+
+            // Normal sequence, a result can only be read once
+            sht.start_measurement(mode).unwrap();
+            sht.get_measurement_result().unwrap();
+            assert_eq!(
+                sht.get_measurement_result_lossy(),
+                Err(Error::MeasurementNotStarted)
+            );
+
+            // A partial start can't be read as a combined result
+            sht.start_temperature_measurement(mode).unwrap();
+            assert_eq!(
+                sht.get_raw_measurement_bytes(),
+                Err(Error::MeasurementNotStarted)
+            );
+
+            // Sleeping discards the started measurement
+            sht.start_temperature_measurement(mode).unwrap();
+            sht.sleep().unwrap();
+            assert_eq!(
+                sht.get_humidity_measurement_result(),
+                Err(Error::MeasurementNotStarted)
+            );
             sht.destroy().done();
         }
-    }
 
-    mod max_measurement_duration {
-        use super::*;
+        /// Test that calibration offsets are applied to converted results
+        /// only.
+        #[test]
+        fn calibration_offsets() {
+            let bytes = alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c];
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, bytes.clone()),
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, bytes[..3].to_vec()),
+                Transaction::write(SHT_ADDR, alloc::vec![0x58, 0xE0]),
+                Transaction::read(SHT_ADDR, bytes[3..].to_vec()),
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, bytes.clone()),
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, bytes.clone()),
+            ];
+            let mode = PowerMode::NormalMode;
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            assert_eq!(sht.calibration(), (0, 0));
+            sht.set_calibration(-800, 2000);
+            assert_eq!(sht.calibration(), (-800, 2000));
+
+            sht.start_measurement(mode).unwrap();
+            let measurement = sht.get_measurement_result().unwrap();
+            assert_eq!(measurement.temperature.as_millidegrees_celsius(), 22_930);
+            assert_eq!(measurement.humidity.as_millipercent(), 64_968);
+
+            sht.start_temperature_measurement(mode).unwrap();
+            let temperature = sht.get_temperature_measurement_result().unwrap();
+            assert_eq!(temperature.as_millidegrees_celsius(), 22_930);
+            sht.start_humidity_measurement(mode).unwrap();
+            let humidity = sht.get_humidity_measurement_result().unwrap();
+            assert_eq!(humidity.as_millipercent(), 64_968);
+
+            // Raw results are not corrected
+            sht.start_measurement(mode).unwrap();
+            let raw = sht.get_raw_measurement_result().unwrap();
+            assert_eq!(
+                Measurement::from(raw).temperature.as_millidegrees_celsius(),
+                23_730
+            );
+
+            // Offsets saturate and humidity stays within 0-100 %RH
+            sht.set_calibration(i32::MAX, 50_000);
+            sht.start_measurement(mode).unwrap();
+            let measurement = sht.get_measurement_result().unwrap();
+            assert_eq!(measurement.temperature.as_millidegrees_celsius(), i32::MAX);
+            assert_eq!(measurement.humidity.as_millipercent(), 100_000);
+            sht.destroy().done();
+        }
 
         #[test]
-        fn shortcut_function() {
-            let c3 = ShtC3::new(I2cMock::new(&[]));
+        fn measure_tagged() {
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            let (measurement, provenance) = sht
+                .measure_tagged(PowerMode::LowPower, &mut NoopDelay)
+                .unwrap();
+            assert_eq!(measurement.temperature.as_millidegrees_celsius(), 23_730);
+            assert_eq!(
+                provenance,
+                MeasurementProvenance {
+                    power_mode: PowerMode::LowPower,
+                    order: MeasurementOrder::TemperatureFirst,
+                }
+            );
+            sht.destroy().done();
+        }
 
-            assert_eq!(c3.max_measurement_duration(PowerMode::NormalMode), 12100);
-            assert_eq!(c3.max_measurement_duration(PowerMode::LowPower), 800);
+        /// Test that a corrupt frame is retried.
+        #[test]
+        fn measure_with_retries() {
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x00]),
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            let measurement = sht
+                .measure_with_retries(PowerMode::NormalMode, &mut NoopDelay, 1)
+                .unwrap();
+            assert_eq!(measurement.temperature.as_millidegrees_celsius(), 23_730);
+            sht.destroy().done();
+        }
+
+        /// Test that CRC errors are returned after exhausting the retries,
+        /// and that I²C errors are not retried.
+        #[test]
+        fn measure_with_retries_exhausted() {
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0x00, 0xa1, 0x33, 0x1c]),
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0x00, 0xa1, 0x33, 0x1c]),
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]).with_error(ErrorKind::Other),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            let err = sht
+                .measure_with_retries(PowerMode::NormalMode, &mut NoopDelay, 1)
+                .unwrap_err();
+            assert_eq!(err, Error::Crc { word_index: 0 });
+            let err = sht
+                .measure_with_retries(PowerMode::NormalMode, &mut NoopDelay, 3)
+                .unwrap_err();
+            assert_eq!(
+                err,
+                Error::I2c {
+                    op: Operation::Measure,
+                    source: ErrorKind::Other
+                }
+            );
+            sht.destroy().done();
+        }
+
+        /// Test that `measure_averaged` runs one measurement per sample.
+        #[test]
+        fn measure_averaged() {
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(
+                    SHT_ADDR,
+                    alloc::vec![0x64, 0x8c, crc8(&[0x64, 0x8c]), 0xa1, 0x33, 0x1c],
+                ),
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(
+                    SHT_ADDR,
+                    alloc::vec![0x64, 0x8d, crc8(&[0x64, 0x8d]), 0xa1, 0x33, 0x1c],
+                ),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            let measurement = sht
+                .measure_averaged(PowerMode::NormalMode, 3, 5000, &mut NoopDelay)
+                .unwrap();
+            assert_eq!(measurement.temperature.as_millidegrees_celsius(), 23_733);
+            assert_eq!(measurement.humidity.as_millipercent(), 62_968);
+            sht.destroy().done();
+        }
+
+        /// Test that `measure_averaged` aborts on the first CRC error.
+        #[test]
+        fn measure_averaged_crc_error() {
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0x00, 0xa1, 0x33, 0x1c]),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            let err = sht
+                .measure_averaged(PowerMode::NormalMode, 3, 5000, &mut NoopDelay)
+                .unwrap_err();
+            assert_eq!(err, Error::Crc { word_index: 0 });
+            sht.destroy().done();
+        }
+
+        /// Test that `measure_with` averages the configured number of samples
+        /// and restores the CRC mode.
+        #[test]
+        fn measure_with_config() {
+            let config = MeasureConfig::new()
+                .power_mode(PowerMode::LowPower)
+                .samples(2)
+                .margin_percent(10)
+                .crc_mode(CrcMode::Skip);
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+                // Invalid checksums are ignored in skip mode
+                Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8c, 0x00, 0xa1, 0x33, 0x00]),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            let measurement = sht.measure_with(&config, &mut NoopDelay).unwrap();
+            assert_eq!(measurement.temperature.as_millidegrees_celsius(), 23_732);
+            assert_eq!(measurement.humidity.as_millipercent(), 62_968);
+            assert_eq!(sht.crc_mode(), CrcMode::Strict);
+            sht.destroy().done();
+        }
+
+        /// Test that agreeing measurements in both orders are averaged.
+        #[test]
+        fn measure_validated() {
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+                // Humidity first, temperature one raw step higher
+                Transaction::write(SHT_ADDR, alloc::vec![0x58, 0xe0]),
+                Transaction::read(
+                    SHT_ADDR,
+                    alloc::vec![0xa1, 0x33, 0x1c, 0x64, 0x8c, crc8(&[0x64, 0x8c])],
+                ),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            let measurement = sht
+                .measure_validated(PowerMode::NormalMode, &mut NoopDelay, 100)
+                .unwrap();
+            assert_eq!(measurement.temperature.as_millidegrees_celsius(), 23_732);
+            assert_eq!(measurement.humidity.as_millipercent(), 62_968);
+            sht.destroy().done();
+        }
+
+        /// Test that disagreeing measurements are rejected.
+        #[test]
+        fn measure_validated_inconsistent() {
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+                // Humidity first, temperature about 1.7 °C higher
+                Transaction::write(SHT_ADDR, alloc::vec![0x58, 0xe0]),
+                Transaction::read(
+                    SHT_ADDR,
+                    alloc::vec![0xa1, 0x33, 0x1c, 0x66, 0x8b, crc8(&[0x66, 0x8b])],
+                ),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            let err = sht
+                .measure_validated(PowerMode::NormalMode, &mut NoopDelay, 100)
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                Error::Inconsistent { temperature_first, humidity_first }
+                    if temperature_first.temperature.as_millidegrees_celsius() == 23_730
+                        && humidity_first.temperature.as_millidegrees_celsius() > 25_000
+            ));
+            sht.destroy().done();
+        }
+
+        #[test]
+        fn raw_measurement_bytes() {
+            let bytes = [
+                0b0110_0100,
+                0b1000_1011,
+                0b1100_0111,
+                0b1010_0001,
+                0b0011_0011,
+                0b0001_1100,
+            ];
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, bytes.to_vec()),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            sht.start_measurement(PowerMode::NormalMode).unwrap();
+            assert_eq!(sht.get_raw_measurement_bytes().unwrap(), bytes);
+            sht.destroy().done();
+        }
+
+        /// Ensure that a humidity first measurement is decoded in the
+        /// correct order.
+        #[test]
+        fn measurement_result_humidity_first() {
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x58, 0xE0]),
+                Transaction::read(
+                    SHT_ADDR,
+                    alloc::vec![
+                        0b1010_0001,
+                        0b0011_0011,
+                        0b0001_1100,
+                        0b0110_0100,
+                        0b1000_1011,
+                        0b1100_0111,
+                    ],
+                ),
+                Transaction::write(SHT_ADDR, alloc::vec![0x58, 0xE0]),
+                Transaction::read(
+                    SHT_ADDR,
+                    alloc::vec![
+                        0b1010_0001,
+                        0b0011_0011,
+                        0b0001_1100,
+                        0b0110_0100,
+                        0b1000_1011,
+                        0b0000_0000,
+                    ],
+                ),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+
+            sht.start_measurement_ordered(PowerMode::NormalMode, MeasurementOrder::HumidityFirst)
+                .unwrap();
+            let measurement = sht.get_measurement_result().unwrap();
+            assert_eq!(measurement.temperature.as_millidegrees_celsius(), 23_730);
+            assert_eq!(measurement.humidity.as_millipercent(), 62_968);
+
+            sht.start_measurement_ordered(PowerMode::NormalMode, MeasurementOrder::HumidityFirst)
+                .unwrap();
+            let partial = sht.get_measurement_result_lossy().unwrap();
+            assert_eq!(partial.temperature, None);
+            assert_eq!(partial.humidity.map(|h| h.as_millipercent()), Some(62_968));
+
+            sht.destroy().done();
+        }
+
+        /// Ensure that a corrupted humidity word still returns the
+        /// temperature in a lossy read.
+        #[test]
+        fn measurement_result_lossy() {
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(
+                    SHT_ADDR,
+                    alloc::vec![
+                        0b0110_0100,
+                        0b1000_1011,
+                        0b1100_0111,
+                        0b1010_0001,
+                        0b0011_0011,
+                        0b0000_0000,
+                    ],
+                ),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            sht.start_measurement(PowerMode::NormalMode).unwrap();
+            let partial = sht.get_measurement_result_lossy().unwrap();
+            assert_eq!(
+                partial.temperature.map(|t| t.as_millidegrees_celsius()),
+                Some(23_730)
+            );
+            assert_eq!(partial.humidity, None);
+            sht.destroy().done();
+        }
+
+        /// Ensure that the failing word is reported on a CRC error.
+        #[test]
+        fn measure_crc_error_word_index() {
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(
+                    SHT_ADDR,
+                    alloc::vec![
+                        0b0110_0100,
+                        0b1000_1011,
+                        0b1100_0111,
+                        0b1010_0001,
+                        0b0011_0011,
+                        0b0000_0000,
+                    ],
+                ),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            let err = sht
+                .measure(PowerMode::NormalMode, &mut NoopDelay)
+                .unwrap_err();
+            assert_eq!(err, Error::Crc { word_index: 1 });
+            sht.destroy().done();
+        }
+
+        /// Test the command bytes sent for each order and power mode.
+        #[test]
+        fn start_measurement_ordered() {
+            let test_data = [
+                (
+                    PowerMode::NormalMode,
+                    MeasurementOrder::TemperatureFirst,
+                    [0x78, 0x66],
+                ),
+                (
+                    PowerMode::NormalMode,
+                    MeasurementOrder::HumidityFirst,
+                    [0x58, 0xE0],
+                ),
+                (
+                    PowerMode::LowPower,
+                    MeasurementOrder::TemperatureFirst,
+                    [0x60, 0x9C],
+                ),
+                (
+                    PowerMode::LowPower,
+                    MeasurementOrder::HumidityFirst,
+                    [0x40, 0x1A],
+                ),
+            ];
+            for (mode, order, command) in test_data {
+                let expectations = [Transaction::write(SHT_ADDR, command.to_vec())];
+                let mock = I2cMock::new(&expectations);
+                let mut sht = ShtC3::new(mock);
+                sht.start_measurement_ordered(mode, order).unwrap();
+                sht.destroy().done();
+            }
+        }
+
+        /// Ensure that I²C write errors are handled when measuring.
+        #[test]
+        fn measure_write_error() {
+            let expectations =
+                [Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C])
+                    .with_error(ErrorKind::Other)];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            let err = sht
+                .measure(PowerMode::LowPower, &mut NoopDelay)
+                .unwrap_err();
+            assert_eq!(
+                err,
+                Error::I2c {
+                    op: Operation::Measure,
+                    source: ErrorKind::Other
+                }
+            );
+            sht.destroy().done();
+        }
+
+        /// Test that I²C errors are tagged with the failed operation.
+        #[test]
+        fn error_operation() {
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17]).with_error(ErrorKind::Other),
+                Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17]),
+                Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c])
+                    .with_error(ErrorKind::Other),
+                Transaction::write(SHT_ADDR, alloc::vec![0xef, 0xc8]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x08, 0x47, 0x2b])
+                    .with_error(ErrorKind::Other),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+
+            let err = sht.wakeup(&mut NoopDelay).unwrap_err();
+            assert_eq!(err.operation(), Some(Operation::WakeUp));
+
+            sht.wakeup(&mut NoopDelay).unwrap();
+            let err = sht
+                .measure(PowerMode::LowPower, &mut NoopDelay)
+                .unwrap_err();
+            assert_eq!(err.operation(), Some(Operation::ReadResult));
+
+            let err = sht.raw_id_register().unwrap_err();
+            assert_eq!(err.operation(), Some(Operation::ReadId));
+            assert_eq!(Error::<ErrorKind>::Asleep.operation(), None);
+
+            // Bus errors converted with `From` have no known operation
+            let err: Error<ErrorKind> = ErrorKind::Other.into();
+            assert_eq!(
+                err,
+                Error::I2c {
+                    op: Operation::Unknown,
+                    source: ErrorKind::Other
+                }
+            );
+
+            sht.destroy().done();
+        }
+    }
+
+    mod self_test {
+        use super::*;
+
+        fn expectations(measurement: [u8; 6]) -> [Transaction; 5] {
+            let crc = crc8(&[0b00001000, 0b00000111]);
+            [
+                Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17]),
+                Transaction::write(SHT_ADDR, alloc::vec![0xef, 0xc8]),
+                Transaction::read(SHT_ADDR, alloc::vec![0b00001000, 0b00000111, crc]),
+                Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+                Transaction::read(SHT_ADDR, measurement.to_vec()),
+            ]
+        }
+
+        #[test]
+        fn self_test_ok() {
+            // Datasheet example values: 23.7°C / 62.9 %RH
+            let expectations = expectations([
+                0b0110_0100,
+                0b1000_1011,
+                0b1100_0111,
+                0b1010_0001,
+                0b0011_0011,
+                0b0001_1100,
+            ]);
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            let measurement = sht.self_test(&mut NoopDelay).unwrap();
+            assert_eq!(measurement.temperature.as_millidegrees_celsius(), 23_730);
+            sht.destroy().done();
+        }
+
+        #[test]
+        fn self_test_implausible() {
+            // Raw temperature of 0x0000 is -45°C, below the operating range
+            let expectations = expectations([
+                0x00,
+                0x00,
+                crc8(&[0x00, 0x00]),
+                0b1010_0001,
+                0b0011_0011,
+                0b0001_1100,
+            ]);
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            let err = sht.self_test(&mut NoopDelay).unwrap_err();
+            assert!(
+                matches!(err, Error::ImplausibleReading(m) if m.temperature.as_millidegrees_celsius() == -45_000)
+            );
+            sht.destroy().done();
+        }
+
+        #[test]
+        fn self_test_wrong_id() {
+            let crc = crc8(&[0x00, 0x00]);
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17]),
+                Transaction::write(SHT_ADDR, alloc::vec![0xef, 0xc8]),
+                Transaction::read(SHT_ADDR, alloc::vec![0x00, 0x00, crc]),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            let err = sht.self_test(&mut NoopDelay).unwrap_err();
+            assert_eq!(err, Error::UnexpectedDevice { got: 0 });
+            sht.destroy().done();
+        }
+    }
+
+    mod power_management {
+        use super::*;
+
+        /// Test that commands unsupported by the SHTC1 are not sent.
+        #[test]
+        fn shtc1_unsupported() {
+            let mock = I2cMock::new(&[]);
+            let mut sht = ShtC3::with_kind(mock, SensorKind::Shtc1);
+            assert_eq!(sht.sleep(), Err(Error::Unsupported));
+            assert_eq!(sht.wakeup(&mut NoopDelay), Err(Error::Unsupported));
+            assert_eq!(
+                sht.measure(PowerMode::LowPower, &mut NoopDelay),
+                Err(Error::Unsupported)
+            );
+            sht.destroy().done();
+        }
+
+        /// Test the `sleep` function.
+        #[test]
+        fn sleep() {
+            let expectations = [Transaction::write(SHT_ADDR, alloc::vec![0xB0, 0x98])];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            sht.sleep().unwrap();
+            sht.destroy().done();
+        }
+
+        /// Test that commands other than wakeup are rejected while asleep,
+        /// without doing any I/O.
+        #[test]
+        fn asleep() {
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0xB0, 0x98]),
+                Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17]),
+                Transaction::write(SHT_ADDR, alloc::vec![0x80, 0x5D]),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            assert!(!sht.is_asleep());
+            sht.sleep().unwrap();
+            assert!(sht.is_asleep());
+
+            assert_eq!(
+                sht.measure(PowerMode::NormalMode, &mut NoopDelay),
+                Err(Error::Asleep)
+            );
+            assert_eq!(sht.device_identifier(), Err(Error::Asleep));
+            assert_eq!(sht.reset(&mut NoopDelay), Err(Error::Asleep));
+            assert_eq!(sht.sleep(), Err(Error::Asleep));
+            sht.set_transaction_mode(TransactionMode::Combined);
+            assert_eq!(sht.raw_id_register(), Err(Error::Asleep));
+
+            sht.wakeup(&mut NoopDelay).unwrap();
+            assert!(!sht.is_asleep());
+            sht.reset(&mut NoopDelay).unwrap();
+            assert!(!sht.is_asleep());
+            sht.destroy().done();
+        }
+
+        /// Test that `wakeup_polled` retries until the sensor acknowledges.
+        #[test]
+        fn wakeup_polled() {
+            let nack = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17]),
+                Transaction::write(SHT_ADDR, alloc::vec![0xef, 0xc8]).with_error(nack),
+                Transaction::write(SHT_ADDR, alloc::vec![0xef, 0xc8]).with_error(nack),
+                Transaction::write(SHT_ADDR, alloc::vec![0xef, 0xc8]),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            sht.wakeup_polled(&mut NoopDelay, 20).unwrap();
+            sht.destroy().done();
+        }
+
+        /// Test that `wakeup_polled` gives up after the wakeup duration, and
+        /// returns other bus errors immediately.
+        #[test]
+        fn wakeup_polled_timeout() {
+            let nack = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17]),
+                Transaction::write(SHT_ADDR, alloc::vec![0xef, 0xc8]).with_error(nack),
+                Transaction::write(SHT_ADDR, alloc::vec![0xef, 0xc8]).with_error(nack),
+                Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17]),
+                Transaction::write(SHT_ADDR, alloc::vec![0xef, 0xc8])
+                    .with_error(ErrorKind::ArbitrationLoss),
+            ];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            assert_eq!(
+                sht.wakeup_polled(&mut NoopDelay, 120),
+                Err(Error::WakeupTimeout)
+            );
+            assert_eq!(
+                sht.wakeup_polled(&mut NoopDelay, 120),
+                Err(Error::I2c {
+                    op: Operation::WakeUp,
+                    source: ErrorKind::ArbitrationLoss
+                })
+            );
+            sht.destroy().done();
+        }
+
+        /// Test the `wakeup` function.
+        #[test]
+        fn wakeup() {
+            let expectations = [Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17])];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            sht.wakeup(&mut NoopDelay).unwrap();
+            sht.destroy().done();
+        }
+
+        /// Test the `reset` function.
+        #[test]
+        fn reset() {
+            let expectations = [Transaction::write(SHT_ADDR, alloc::vec![0x80, 0x5D])];
+            let mock = I2cMock::new(&expectations);
+            let mut sht = ShtC3::new(mock);
+            sht.reset(&mut NoopDelay).unwrap();
+            sht.destroy().done();
+        }
+    }
+
+    mod shared_bus {
+        use super::*;
+        use ::core::cell::RefCell;
+        use embedded_hal_bus::i2c::RefCellDevice;
+
+        /// Test two drivers and a foreign device sharing the same bus.
+        #[test]
+        fn refcell_device() {
+            const OTHER_ADDR: u8 = 0x23;
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+                Transaction::write(OTHER_ADDR, alloc::vec![0x10]),
+                Transaction::read(
+                    SHT_ADDR,
+                    alloc::vec![
+                        0b0110_0100,
+                        0b1000_1011,
+                        0b1100_0111,
+                        0b1010_0001,
+                        0b0011_0011,
+                        0b0001_1100,
+                    ],
+                ),
+                Transaction::write(0x44, alloc::vec![0xB0, 0x98]),
+            ];
+            let bus = RefCell::new(I2cMock::new(&expectations));
+
+            let mut sht = ShtC3::new(RefCellDevice::new(&bus));
+            let mut other_sht = ShtC3::new_with_address(RefCellDevice::new(&bus), 0x44);
+            let mut other = RefCellDevice::new(&bus);
+
+            sht.start_measurement(PowerMode::LowPower).unwrap();
+            other.write(OTHER_ADDR, &[0x10]).unwrap();
+            let measurement = sht.get_measurement_result().unwrap();
+            assert_eq!(measurement.temperature.as_millidegrees_celsius(), 23_730);
+            other_sht.sleep().unwrap();
+
+            bus.into_inner().done();
+        }
+
+        /// Test a driver borrowing the bus through a mutable reference.
+        #[test]
+        fn mutable_reference() {
+            let expectations = [Transaction::write(SHT_ADDR, alloc::vec![0xB0, 0x98])];
+            let mut mock = I2cMock::new(&expectations);
+
+            let mut sht = ShtC3::new(&mut mock);
+            sht.sleep().unwrap();
+            sht.destroy();
+
+            mock.done();
+        }
+
+        /// Test that drivers on two different bus types behave identically,
+        /// as both share the bus independent logic.
+        #[test]
+        fn two_instantiations() {
+            fn run<I2C: I2c>(sht: &mut ShtC3<I2C>) -> [Result<Measurement, Error<I2C::Error>>; 3] {
+                sht.set_calibration(-500, 1_000);
+                ::core::array::from_fn(|_| {
+                    sht.start_measurement(PowerMode::LowPower)?;
+                    sht.get_measurement_result()
+                })
+            }
+
+            let frames: [&[u8]; 3] = [
+                &[0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c],
+                &[0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1d],
+                &[0x00, 0x00, 0x81, 0xff, 0xff, 0xac],
+            ];
+            let expectations: alloc::vec::Vec<_> = frames
+                .iter()
+                .flat_map(|frame| {
+                    [
+                        Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+                        Transaction::read(SHT_ADDR, frame.to_vec()),
+                    ]
+                })
+                .collect();
+
+            let mut owned = ShtC3::new(I2cMock::new(&expectations));
+            let owned_results = run(&mut owned);
+            owned.destroy().done();
+
+            let bus = RefCell::new(I2cMock::new(&expectations));
+            let mut shared = ShtC3::new(RefCellDevice::new(&bus));
+            let shared_results = run(&mut shared);
+            bus.into_inner().done();
+
+            assert_eq!(owned_results, shared_results);
+            assert_eq!(owned_results[1], Err(Error::Crc { word_index: 1 }));
+            assert_eq!(
+                owned_results[2]
+                    .as_ref()
+                    .map(|m| m.temperature.as_millidegrees_celsius()),
+                Ok(-45_500)
+            );
+        }
+    }
+
+    mod max_measurement_duration {
+        use super::*;
+
+        #[test]
+        fn shortcut_function() {
+            let c3 = ShtC3::new(I2cMock::new(&[]));
+
+            assert_eq!(c3.max_measurement_duration(PowerMode::NormalMode), 12100);
+            assert_eq!(c3.max_measurement_duration(PowerMode::LowPower), 800);
+
+            c3.destroy().done();
+        }
+
+        #[test]
+        fn associated_constants() {
+            type Sht = ShtC3<I2cMock>;
+            const TICKS: [u32; 2] = [Sht::MEASURE_NORMAL_US, Sht::MEASURE_LOWPOWER_US];
+
+            let c3 = ShtC3::new(I2cMock::new(&[]));
+
+            assert_eq!(c3.max_measurement_duration(PowerMode::NormalMode), TICKS[0]);
+            assert_eq!(c3.max_measurement_duration(PowerMode::LowPower), TICKS[1]);
+            assert_eq!(c3.wakeup_duration(), Sht::WAKEUP_DURATION_US);
+            assert_eq!(c3.reset_duration(), Sht::RESET_DURATION_US);
+
+            c3.destroy().done();
+        }
+
+        #[cfg(feature = "fugit")]
+        #[test]
+        fn typed_durations() {
+            use fugit::MicrosDurationU32;
+
+            let c3 = ShtC3::new(I2cMock::new(&[]));
+
+            assert_eq!(
+                c3.max_measurement_duration_t(PowerMode::NormalMode),
+                MicrosDurationU32::micros(12_100)
+            );
+            assert_eq!(
+                c3.max_measurement_duration_t(PowerMode::LowPower),
+                MicrosDurationU32::micros(800)
+            );
+            assert_eq!(c3.wakeup_duration_t(), MicrosDurationU32::micros(240));
+            assert_eq!(c3.reset_duration_t(), MicrosDurationU32::micros(240));
+
+            c3.destroy().done();
+        }
+
+        #[test]
+        fn with_margin() {
+            let c3 = ShtC3::new(I2cMock::new(&[]));
+
+            assert_eq!(
+                c3.max_measurement_duration_with_margin(PowerMode::NormalMode, 0),
+                12100
+            );
+            assert_eq!(
+                c3.max_measurement_duration_with_margin(PowerMode::NormalMode, 20),
+                14520
+            );
+            assert_eq!(
+                c3.max_measurement_duration_with_margin(PowerMode::LowPower, 20),
+                960
+            );
+            assert_eq!(
+                c3.max_measurement_duration_with_margin(PowerMode::LowPower, u32::MAX),
+                800 + u32::MAX / 100
+            );
+
+            c3.destroy().done();
+        }
+    }
+
+    /// Check every duration getter against the ranges of the datasheet
+    /// (SHTC3 datasheet 3.1 and table 5), so a unit mixup can't slip by.
+    mod durations {
+        use super::*;
+
+        use embedded_hal_mock::eh1::delay::{CheckedDelay, Transaction as DelayTransaction};
+
+        #[test]
+        fn datasheet_ranges() {
+            let c3 = ShtC3::new(I2cMock::new(&[]));
+
+            // Wakeup and soft reset: 180-240 µs
+            assert!((180..=240).contains(&c3.wakeup_duration()));
+            assert!((180..=240).contains(&c3.reset_duration()));
+
+            // Normal mode: typically 10.8 ms, at most 12.1 ms
+            let typical = c3.typical_measurement_duration(PowerMode::NormalMode);
+            let max = c3.max_measurement_duration(PowerMode::NormalMode);
+            assert_eq!((typical, max), (10_800, 12_100));
+
+            // Low power mode: typically 0.7 ms, at most 0.8 ms
+            let typical = c3.typical_measurement_duration(PowerMode::LowPower);
+            let max = c3.max_measurement_duration(PowerMode::LowPower);
+            assert_eq!((typical, max), (700, 800));
+
+            c3.destroy().done();
+        }
+
+        /// Test that the trait getters use the same unit as the inherent
+        /// ones.
+        #[test]
+        fn trait_getters() {
+            let c3 = ShtC3::new(I2cMock::new(&[]));
+            let sensor: &dyn DynSensor = &c3;
+
+            assert_eq!(sensor.wakeup_duration(), c3.wakeup_duration());
+            assert_eq!(sensor.reset_duration(), c3.reset_duration());
+            for mode in [PowerMode::NormalMode, PowerMode::LowPower] {
+                assert_eq!(
+                    sensor.max_measurement_duration(mode),
+                    c3.max_measurement_duration(mode)
+                );
+            }
+
+            c3.destroy().done();
+        }
+
+        /// Test that the blocking functions wait for the durations in
+        /// microseconds.
+        #[test]
+        fn blocking_delays() {
+            let expectations = [
+                Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17]),
+                Transaction::write(SHT_ADDR, alloc::vec![0x80, 0x5D]),
+            ];
+            let mut sht = ShtC3::new(I2cMock::new(&expectations));
+            let mut delay = CheckedDelay::new(&[
+                DelayTransaction::delay_us(240),
+                DelayTransaction::delay_us(240),
+            ]);
+
+            sht.wakeup(&mut delay).unwrap();
+            sht.reset(&mut delay).unwrap();
+
+            delay.done();
+            sht.destroy().done();
+        }
+
+        #[cfg(feature = "fugit")]
+        #[test]
+        fn typed_durations_match() {
+            let c3 = ShtC3::new(I2cMock::new(&[]));
+
+            assert_eq!(c3.wakeup_duration_t().to_micros(), c3.wakeup_duration());
+            assert_eq!(c3.reset_duration_t().to_micros(), c3.reset_duration());
+            for mode in [PowerMode::NormalMode, PowerMode::LowPower] {
+                assert_eq!(
+                    c3.max_measurement_duration_t(mode).to_micros(),
+                    c3.max_measurement_duration(mode)
+                );
+            }
 
             c3.destroy().done();
         }
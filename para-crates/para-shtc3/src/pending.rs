@@ -0,0 +1,171 @@
+use crate::{MeasurementOrder, PowerMode};
+
+/// A started measurement, created by [`ShtC3::start_measurement_timed`].
+///
+/// Keeps the power mode and the caller supplied start timestamp, so the time
+/// until the result can be read doesn't need to be tracked separately. The
+/// token is consumed by
+/// [`ShtC3::get_pending_measurement_result`], so each measurement is read at
+/// most once. Timestamps are in microseconds on whatever clock the caller
+/// uses, no clock is read by the driver.
+///
+/// [`ShtC3::start_measurement_timed`]: crate::ShtC3::start_measurement_timed
+/// [`ShtC3::get_pending_measurement_result`]: crate::ShtC3::get_pending_measurement_result
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Pending {
+    mode: PowerMode,
+    pub(crate) order: MeasurementOrder,
+    started_at_us: u64,
+    duration_us: u32,
+}
+
+impl Pending {
+    pub(crate) const fn new(
+        mode: PowerMode,
+        order: MeasurementOrder,
+        started_at_us: u64,
+        duration_us: u32,
+    ) -> Self {
+        Self {
+            mode,
+            order,
+            started_at_us,
+            duration_us,
+        }
+    }
+
+    /// Return the power mode of the measurement.
+    #[inline]
+    pub const fn mode(&self) -> PowerMode {
+        self.mode
+    }
+
+    /// Return the timestamp the measurement was started at.
+    #[inline]
+    pub const fn started_at_us(&self) -> u64 {
+        self.started_at_us
+    }
+
+    /// Return the earliest timestamp the result is guaranteed to be ready
+    /// at, based on the maximum measurement duration.
+    #[inline]
+    pub const fn ready_at_us(&self) -> u64 {
+        self.started_at_us.saturating_add(self.duration_us as u64)
+    }
+
+    /// Return the time left until the result is ready, or 0 if it already
+    /// is.
+    #[inline]
+    pub const fn remaining_us(&self, now_us: u64) -> u64 {
+        self.ready_at_us().saturating_sub(now_us)
+    }
+
+    /// Check whether the result is ready to be read.
+    #[inline]
+    pub const fn is_ready(&self, now_us: u64) -> bool {
+        now_us >= self.ready_at_us()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use crate::{Error, ShtC3, tests::SHT_ADDR};
+
+    use super::*;
+
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+    #[test]
+    fn remaining_time() {
+        let pending = Pending::new(
+            PowerMode::NormalMode,
+            MeasurementOrder::TemperatureFirst,
+            1_000,
+            12_100,
+        );
+
+        assert_eq!(pending.ready_at_us(), 13_100);
+        assert_eq!(pending.remaining_us(1_000), 12_100);
+        assert_eq!(pending.remaining_us(13_000), 100);
+        assert!(!pending.is_ready(13_099));
+
+        // Already elapsed
+        assert_eq!(pending.remaining_us(13_100), 0);
+        assert_eq!(pending.remaining_us(20_000), 0);
+        assert!(pending.is_ready(13_100));
+        assert!(pending.is_ready(20_000));
+
+        // Started right before the clock wraps
+        let pending = Pending::new(
+            PowerMode::LowPower,
+            MeasurementOrder::TemperatureFirst,
+            u64::MAX - 10,
+            800,
+        );
+        assert_eq!(pending.ready_at_us(), u64::MAX);
+    }
+
+    #[test]
+    fn start_and_read() {
+        let expectations = [
+            Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+            Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+        ];
+        let mut sht = ShtC3::new(I2cMock::new(&expectations));
+
+        let pending = sht
+            .start_measurement_timed(PowerMode::LowPower, 500)
+            .unwrap();
+        assert_eq!(pending.mode(), PowerMode::LowPower);
+        assert_eq!(pending.started_at_us(), 500);
+        assert_eq!(pending.ready_at_us(), 1_300);
+
+        let measurement = sht.get_pending_measurement_result(pending).unwrap();
+        assert_eq!(measurement.temperature.as_millidegrees_celsius(), 23_730);
+        sht.destroy().done();
+    }
+
+    /// Test that a token doesn't read the result of a measurement started
+    /// with a different order.
+    #[test]
+    fn order_mismatch() {
+        let expectations = [
+            Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+            Transaction::write(SHT_ADDR, alloc::vec![0x40, 0x1A]),
+        ];
+        let mut sht = ShtC3::new(I2cMock::new(&expectations));
+
+        let pending = sht.start_measurement_timed(PowerMode::LowPower, 0).unwrap();
+        sht.start_measurement_ordered(PowerMode::LowPower, MeasurementOrder::HumidityFirst)
+            .unwrap();
+
+        assert_eq!(
+            sht.get_pending_measurement_result(pending),
+            Err(Error::MeasurementNotStarted)
+        );
+        sht.destroy().done();
+    }
+
+    /// Test that a token doesn't read the result of a measurement started
+    /// in a different power mode.
+    #[test]
+    fn mode_mismatch() {
+        let expectations = [
+            Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+            Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+        ];
+        let mut sht = ShtC3::new(I2cMock::new(&expectations));
+
+        let pending = sht.start_measurement_timed(PowerMode::LowPower, 0).unwrap();
+        sht.start_measurement(PowerMode::NormalMode).unwrap();
+
+        assert_eq!(
+            sht.get_pending_measurement_result(pending),
+            Err(Error::MeasurementNotStarted)
+        );
+        sht.destroy().done();
+    }
+}
@@ -0,0 +1,409 @@
+use embedded_hal::i2c::{self, I2c, SevenBitAddress};
+
+use crate::{Error, Measurement, Operation, PowerMode, ShtC3};
+
+/// A non-blocking temperature / humidity sensor.
+///
+/// This allows application code to drive different sensors with the same
+/// measurement loop. Waiting for the returned durations is left to the
+/// caller, so the trait can be used from both blocking and async code.
+pub trait TemperatureHumiditySensor {
+    /// The error returned by the sensor.
+    type Error;
+
+    /// Start waking up the sensor from sleep mode.
+    fn start_wakeup(&mut self) -> Result<(), Self::Error>;
+
+    /// Return the time (in µs) the sensor needs to wake up.
+    fn wakeup_duration(&self) -> u32;
+
+    /// Put the sensor into sleep mode.
+    fn sleep(&mut self) -> Result<(), Self::Error>;
+
+    /// Start a combined temperature / humidity measurement.
+    fn start_measurement(&mut self, mode: PowerMode) -> Result<(), Self::Error>;
+
+    /// Return the maximum time (in µs) a measurement in the given mode takes.
+    fn max_measurement_duration(&self, mode: PowerMode) -> u32;
+
+    /// Read the result of the started measurement.
+    fn get_measurement_result(&mut self) -> Result<Measurement, Self::Error>;
+
+    /// Start a soft reset of the sensor.
+    fn start_reset(&mut self) -> Result<(), Self::Error>;
+
+    /// Return the time (in µs) the sensor needs to reset.
+    fn reset_duration(&self) -> u32;
+}
+
+impl<T> TemperatureHumiditySensor for &mut T
+where
+    T: TemperatureHumiditySensor + ?Sized,
+{
+    type Error = T::Error;
+
+    fn start_wakeup(&mut self) -> Result<(), Self::Error> {
+        T::start_wakeup(self)
+    }
+
+    fn wakeup_duration(&self) -> u32 {
+        T::wakeup_duration(self)
+    }
+
+    fn sleep(&mut self) -> Result<(), Self::Error> {
+        T::sleep(self)
+    }
+
+    fn start_measurement(&mut self, mode: PowerMode) -> Result<(), Self::Error> {
+        T::start_measurement(self, mode)
+    }
+
+    fn max_measurement_duration(&self, mode: PowerMode) -> u32 {
+        T::max_measurement_duration(self, mode)
+    }
+
+    fn get_measurement_result(&mut self) -> Result<Measurement, Self::Error> {
+        T::get_measurement_result(self)
+    }
+
+    fn start_reset(&mut self) -> Result<(), Self::Error> {
+        T::start_reset(self)
+    }
+
+    fn reset_duration(&self) -> u32 {
+        T::reset_duration(self)
+    }
+}
+
+impl<I2C> TemperatureHumiditySensor for ShtC3<I2C>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    type Error = Error<I2C::Error>;
+
+    fn start_wakeup(&mut self) -> Result<(), Self::Error> {
+        ShtC3::start_wakeup(self)
+    }
+
+    fn wakeup_duration(&self) -> u32 {
+        ShtC3::wakeup_duration(self)
+    }
+
+    fn sleep(&mut self) -> Result<(), Self::Error> {
+        ShtC3::sleep(self)
+    }
+
+    fn start_measurement(&mut self, mode: PowerMode) -> Result<(), Self::Error> {
+        ShtC3::start_measurement(self, mode)
+    }
+
+    fn max_measurement_duration(&self, mode: PowerMode) -> u32 {
+        ShtC3::max_measurement_duration(self, mode)
+    }
+
+    fn get_measurement_result(&mut self) -> Result<Measurement, Self::Error> {
+        ShtC3::get_measurement_result(self)
+    }
+
+    fn start_reset(&mut self) -> Result<(), Self::Error> {
+        ShtC3::start_reset(self)
+    }
+
+    fn reset_duration(&self) -> u32 {
+        ShtC3::reset_duration(self)
+    }
+}
+
+/// A driver error with the I²C bus error type erased, returned by
+/// [`DynSensor`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DriverError {
+    /// I²C bus error
+    I2c {
+        /// The operation that failed
+        op: Operation,
+        /// The kind of the I²C bus error
+        kind: i2c::ErrorKind,
+    },
+    /// CRC checksum validation failed
+    Crc,
+    /// The command is not supported by the sensor kind
+    Unsupported,
+    /// A command other than wakeup was sent while the sensor is asleep
+    Asleep,
+    /// The sensor did not respond in time
+    Timeout,
+    /// A measurement result was read without starting a matching measurement
+    MeasurementNotStarted,
+    /// The sensor returned unexpected or implausible data
+    InvalidData,
+}
+
+impl<E> From<Error<E>> for DriverError
+where
+    E: i2c::Error,
+{
+    fn from(error: Error<E>) -> Self {
+        match error {
+            Error::I2c { op, source } => Self::I2c {
+                op,
+                kind: source.kind(),
+            },
+            Error::Crc { .. } => Self::Crc,
+            Error::Unsupported => Self::Unsupported,
+            Error::Asleep => Self::Asleep,
+            Error::WakeupTimeout | Error::Timeout => Self::Timeout,
+            Error::MeasurementNotStarted => Self::MeasurementNotStarted,
+            Error::UnexpectedDevice { .. }
+            | Error::InvalidLength
+            | Error::ImplausibleReading(_)
+            | Error::Inconsistent { .. } => Self::InvalidData,
+        }
+    }
+}
+
+/// An object-safe version of [`TemperatureHumiditySensor`], with the error
+/// type erased into a [`DriverError`].
+///
+/// This is implemented for [`ShtC3`] with any I²C bus, so application code
+/// can take a `&mut dyn DynSensor` and be tested with a fake sensor instead.
+pub trait DynSensor {
+    /// Start waking up the sensor from sleep mode.
+    fn start_wakeup(&mut self) -> Result<(), DriverError>;
+
+    /// Return the time (in µs) the sensor needs to wake up.
+    fn wakeup_duration(&self) -> u32;
+
+    /// Put the sensor into sleep mode.
+    fn sleep(&mut self) -> Result<(), DriverError>;
+
+    /// Start a combined temperature / humidity measurement.
+    fn start_measurement(&mut self, mode: PowerMode) -> Result<(), DriverError>;
+
+    /// Return the maximum time (in µs) a measurement in the given mode takes.
+    fn max_measurement_duration(&self, mode: PowerMode) -> u32;
+
+    /// Read the result of the started measurement.
+    fn get_measurement_result(&mut self) -> Result<Measurement, DriverError>;
+
+    /// Start a soft reset of the sensor.
+    fn start_reset(&mut self) -> Result<(), DriverError>;
+
+    /// Return the time (in µs) the sensor needs to reset.
+    fn reset_duration(&self) -> u32;
+}
+
+/// Implement [`DynSensor`] by forwarding to [`TemperatureHumiditySensor`].
+macro_rules! forward_dyn_sensor {
+    () => {
+        fn start_wakeup(&mut self) -> Result<(), DriverError> {
+            TemperatureHumiditySensor::start_wakeup(self).map_err(Into::into)
+        }
+
+        fn wakeup_duration(&self) -> u32 {
+            TemperatureHumiditySensor::wakeup_duration(self)
+        }
+
+        fn sleep(&mut self) -> Result<(), DriverError> {
+            TemperatureHumiditySensor::sleep(self).map_err(Into::into)
+        }
+
+        fn start_measurement(&mut self, mode: PowerMode) -> Result<(), DriverError> {
+            TemperatureHumiditySensor::start_measurement(self, mode).map_err(Into::into)
+        }
+
+        fn max_measurement_duration(&self, mode: PowerMode) -> u32 {
+            TemperatureHumiditySensor::max_measurement_duration(self, mode)
+        }
+
+        fn get_measurement_result(&mut self) -> Result<Measurement, DriverError> {
+            TemperatureHumiditySensor::get_measurement_result(self).map_err(Into::into)
+        }
+
+        fn start_reset(&mut self) -> Result<(), DriverError> {
+            TemperatureHumiditySensor::start_reset(self).map_err(Into::into)
+        }
+
+        fn reset_duration(&self) -> u32 {
+            TemperatureHumiditySensor::reset_duration(self)
+        }
+    };
+}
+
+#[cfg(feature = "test-util")]
+pub(crate) use forward_dyn_sensor;
+
+impl<I2C> DynSensor for ShtC3<I2C>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    forward_dyn_sensor!();
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use super::*;
+
+    use crate::{Humidity, MeasurementAccumulator, Temperature, tests::SHT_ADDR};
+
+    use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction};
+
+    /// A sensor returning a fixed sequence of temperatures.
+    struct MockSensor {
+        temperatures: &'static [i32],
+        started: usize,
+        read: usize,
+        awake: bool,
+    }
+
+    impl TemperatureHumiditySensor for MockSensor {
+        type Error = ();
+
+        fn start_wakeup(&mut self) -> Result<(), Self::Error> {
+            self.awake = true;
+            Ok(())
+        }
+
+        fn wakeup_duration(&self) -> u32 {
+            240
+        }
+
+        fn sleep(&mut self) -> Result<(), Self::Error> {
+            self.awake = false;
+            Ok(())
+        }
+
+        fn start_measurement(&mut self, _mode: PowerMode) -> Result<(), Self::Error> {
+            if !self.awake {
+                return Err(());
+            }
+            self.started += 1;
+            Ok(())
+        }
+
+        fn max_measurement_duration(&self, _mode: PowerMode) -> u32 {
+            800
+        }
+
+        fn get_measurement_result(&mut self) -> Result<Measurement, Self::Error> {
+            if self.read == self.started {
+                return Err(());
+            }
+            let temperature = *self.temperatures.get(self.read).ok_or(())?;
+            self.read += 1;
+            Ok(Measurement {
+                temperature: Temperature::from_millidegrees_celsius(temperature),
+                humidity: Humidity::from_millipercent(50_000),
+            })
+        }
+
+        fn start_reset(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn reset_duration(&self) -> u32 {
+            240
+        }
+    }
+
+    /// The averaging loop of the firmware's measurement task.
+    fn measure<S: TemperatureHumiditySensor>(mut sensor: S) -> Result<Measurement, S::Error> {
+        sensor.start_wakeup()?;
+
+        let mode = PowerMode::LowPower;
+        let mut acc = MeasurementAccumulator::new();
+
+        for _ in 0..4 {
+            sensor.start_measurement(mode)?;
+            acc.add(sensor.get_measurement_result()?);
+        }
+
+        sensor.sleep()?;
+
+        Ok(acc.mean().unwrap_or_default())
+    }
+
+    #[test]
+    fn averaging_loop() {
+        let mut sensor = MockSensor {
+            temperatures: &[20_000, 21_000, 22_000, 23_000],
+            started: 0,
+            read: 0,
+            awake: false,
+        };
+
+        let measurement = measure(&mut sensor).unwrap();
+        assert_eq!(measurement.temperature.as_millidegrees_celsius(), 21_500);
+        assert_eq!(measurement.humidity.as_millipercent(), 50_000);
+        assert_eq!(sensor.read, 4);
+        assert!(!sensor.awake);
+
+        // Too few samples are reported as an error
+        sensor.read = 0;
+        sensor.started = 0;
+        sensor.temperatures = &[20_000];
+        assert_eq!(measure(&mut sensor), Err(()));
+    }
+
+    #[test]
+    fn shtc3_sensor() {
+        let expectations = [
+            Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17]),
+            Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+            Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+            Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+            Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+            Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+            Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+            Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+            Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+            Transaction::write(SHT_ADDR, alloc::vec![0xB0, 0x98]),
+        ];
+        let mut sht = ShtC3::new(I2cMock::new(&expectations));
+
+        let measurement = measure(&mut sht).unwrap();
+        assert_eq!(measurement.temperature.as_millidegrees_celsius(), 23_730);
+        assert_eq!(measurement.humidity.as_millipercent(), 62_968);
+        sht.destroy().done();
+    }
+
+    /// The averaging loop of the firmware's measurement task, on a trait
+    /// object.
+    fn measure_dyn(sensor: &mut dyn DynSensor) -> Result<Measurement, DriverError> {
+        let mode = PowerMode::LowPower;
+        sensor.start_measurement(mode)?;
+        let measurement = sensor.get_measurement_result()?;
+        sensor.sleep()?;
+        Ok(measurement)
+    }
+
+    #[test]
+    fn dyn_sensor() {
+        let expectations = [
+            Transaction::write(SHT_ADDR, alloc::vec![0x60, 0x9C]),
+            Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+            Transaction::write(SHT_ADDR, alloc::vec![0xB0, 0x98]),
+            Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17]).with_error(i2c::ErrorKind::Other),
+        ];
+        let mut sht = ShtC3::new(I2cMock::new(&expectations));
+
+        let measurement = measure_dyn(&mut sht).unwrap();
+        assert_eq!(measurement.temperature.as_millidegrees_celsius(), 23_730);
+
+        // The sensor is asleep now
+        assert_eq!(measure_dyn(&mut sht), Err(DriverError::Asleep));
+
+        // Bus errors keep the operation and error kind
+        assert_eq!(
+            DynSensor::start_wakeup(&mut sht),
+            Err(DriverError::I2c {
+                op: Operation::WakeUp,
+                kind: i2c::ErrorKind::Other
+            })
+        );
+        sht.destroy().done();
+    }
+}
@@ -0,0 +1,165 @@
+use core::ops::{Deref, DerefMut};
+
+use embedded_hal::{
+    delay::DelayNs,
+    i2c::{I2c, SevenBitAddress},
+};
+
+use crate::{Error, ShtC3};
+
+/// A guard keeping the sensor awake, created by [`ShtC3::awake_session`].
+///
+/// The driver's methods are available through the guard. The sensor is put
+/// back to sleep by [`finish`](Self::finish), or on a best-effort basis when
+/// the guard is dropped, e.g. after returning early on an error. Errors while
+/// sending the sleep command on drop can't be reported, so use `finish` when
+/// they matter.
+pub struct AwakeSession<'a, I2C>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    sht: &'a mut ShtC3<I2C>,
+    finished: bool,
+}
+
+impl<'a, I2C> AwakeSession<'a, I2C>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    pub(crate) fn new(
+        sht: &'a mut ShtC3<I2C>,
+        delay: &mut impl DelayNs,
+    ) -> Result<Self, Error<I2C::Error>> {
+        if sht.kind().supports_sleep() {
+            sht.wakeup(delay)?;
+        }
+
+        Ok(Self {
+            sht,
+            finished: false,
+        })
+    }
+
+    /// End the session, putting the sensor back to sleep.
+    pub fn finish(mut self) -> Result<(), Error<I2C::Error>> {
+        self.finished = true;
+        self.sleep_if_supported()
+    }
+
+    fn sleep_if_supported(&mut self) -> Result<(), Error<I2C::Error>> {
+        if self.sht.kind().supports_sleep() {
+            self.sht.sleep()
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<I2C> Deref for AwakeSession<'_, I2C>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    type Target = ShtC3<I2C>;
+
+    fn deref(&self) -> &Self::Target {
+        self.sht
+    }
+}
+
+impl<I2C> DerefMut for AwakeSession<'_, I2C>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.sht
+    }
+}
+
+impl<I2C> Drop for AwakeSession<'_, I2C>
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = self.sleep_if_supported();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use crate::{Operation, PowerMode, SensorKind, tests::SHT_ADDR};
+
+    use super::*;
+
+    use embedded_hal::i2c::ErrorKind;
+    use embedded_hal_mock::eh1::{
+        delay::NoopDelay,
+        i2c::{Mock as I2cMock, Transaction},
+    };
+
+    #[test]
+    fn finish() {
+        let expectations = [
+            Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17]),
+            Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]),
+            Transaction::read(SHT_ADDR, alloc::vec![0x64, 0x8b, 0xc7, 0xa1, 0x33, 0x1c]),
+            Transaction::write(SHT_ADDR, alloc::vec![0xB0, 0x98]),
+        ];
+        let mut sht = ShtC3::new(I2cMock::new(&expectations));
+
+        let mut session = sht.awake_session(&mut NoopDelay).unwrap();
+        let measurement = session
+            .measure(PowerMode::NormalMode, &mut NoopDelay)
+            .unwrap();
+        assert_eq!(measurement.temperature.as_millidegrees_celsius(), 23_730);
+        session.finish().unwrap();
+
+        assert!(sht.is_asleep());
+        sht.destroy().done();
+    }
+
+    /// Test that the sensor is put to sleep when the session is dropped
+    /// after an error.
+    #[test]
+    fn sleep_on_drop() {
+        let expectations = [
+            Transaction::write(SHT_ADDR, alloc::vec![0x35, 0x17]),
+            Transaction::write(SHT_ADDR, alloc::vec![0x78, 0x66]).with_error(ErrorKind::Other),
+            Transaction::write(SHT_ADDR, alloc::vec![0xB0, 0x98]),
+        ];
+        let mut sht = ShtC3::new(I2cMock::new(&expectations));
+
+        let result = (|| {
+            let mut session = sht.awake_session(&mut NoopDelay)?;
+            let measurement = session.measure(PowerMode::NormalMode, &mut NoopDelay)?;
+            session.finish()?;
+            Ok(measurement)
+        })();
+        assert_eq!(
+            result,
+            Err(Error::I2c {
+                op: Operation::Measure,
+                source: ErrorKind::Other
+            })
+        );
+
+        assert!(sht.is_asleep());
+        sht.destroy().done();
+    }
+
+    /// Test that sensors without sleep mode are neither woken up nor put to
+    /// sleep.
+    #[test]
+    fn without_sleep_mode() {
+        let mut sht = ShtC3::with_kind(I2cMock::new(&[]), SensorKind::Shtc1);
+
+        let session = sht.awake_session(&mut NoopDelay).unwrap();
+        session.finish().unwrap();
+        drop(sht.awake_session(&mut NoopDelay).unwrap());
+
+        sht.destroy().done();
+    }
+}
@@ -1,23 +1,144 @@
+use crate::conversion::{
+    humidity_from_raw, raw_from_millidegrees, raw_from_millipercent, temperature_from_raw,
+};
+use crate::crc::crc8;
+
 /// A temperature measurement.
-#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
-#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+///
+/// With the `serde` feature, this is serialized as an integer in
+/// millidegrees Celsius.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Temperature(i32);
 
 /// A humidity measurement.
-#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
-#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+///
+/// With the `serde` feature, this is serialized as an integer in
+/// millipercent relative humidity.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Humidity(i32);
 
 /// A combined temperature / humidity measurement.
+///
+/// With the `serde` feature, this is serialized as a struct with the fields
+/// `temperature_millidegrees_celsius` and `humidity_millipercent`.
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
-#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Measurement {
     /// The measured temperature.
+    #[cfg_attr(feature = "serde", serde(rename = "temperature_millidegrees_celsius"))]
     pub temperature: Temperature,
     /// The measured humidity.
+    #[cfg_attr(feature = "serde", serde(rename = "humidity_millipercent"))]
     pub humidity: Humidity,
 }
 
+impl Measurement {
+    /// Return the component-wise minimum of two measurements.
+    pub fn min(self, other: Self) -> Self {
+        Self {
+            temperature: self.temperature.min(other.temperature),
+            humidity: self.humidity.min(other.humidity),
+        }
+    }
+
+    /// Return the component-wise maximum of two measurements.
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            temperature: self.temperature.max(other.temperature),
+            humidity: self.humidity.max(other.humidity),
+        }
+    }
+
+    /// Check that both temperature and humidity are within the sensor's
+    /// specified operating range, see [`Temperature::is_plausible`] and
+    /// [`Humidity::is_plausible`].
+    pub const fn is_plausible(&self) -> bool {
+        self.temperature.is_plausible() && self.humidity.is_plausible()
+    }
+
+    /// Return the measurement as a tuple of degrees celsius and %RH.
+    pub const fn as_tuple(&self) -> (f32, f32) {
+        (
+            self.temperature.as_degrees_celsius(),
+            self.humidity.as_percent(),
+        )
+    }
+
+    /// Return the NOAA heat index in milli-degrees celsius.
+    ///
+    /// Uses the Rothfusz regression, including the NOAA adjustments for low
+    /// and high humidity. Below a heat index of around 27 °C, the simpler
+    /// Steadman approximation is used instead, as the regression is not valid
+    /// in that range.
+    #[cfg(feature = "libm")]
+    pub fn heat_index_millidegrees_celsius(&self) -> i32 {
+        let t = self.temperature.as_degrees_celsius() * 1.8 + 32.0;
+        let rh = self.humidity.as_percent();
+
+        let simple = 0.5 * (t + 61.0 + ((t - 68.0) * 1.2) + (rh * 0.094));
+
+        let hi = if (simple + t) / 2.0 < 80.0 {
+            simple
+        } else {
+            let mut hi = -42.379 + 2.049_015_2 * t + 10.143_331 * rh
+                - 0.224_755_4 * t * rh
+                - 0.006_837_83 * t * t
+                - 0.054_817_17 * rh * rh
+                + 0.001_228_74 * t * t * rh
+                + 0.000_852_82 * t * rh * rh
+                - 0.000_001_99 * t * t * rh * rh;
+
+            if rh < 13.0 && (80.0..=112.0).contains(&t) {
+                hi -= ((13.0 - rh) / 4.0) * libm::sqrtf((17.0 - libm::fabsf(t - 95.0)) / 17.0);
+            } else if rh > 85.0 && (80.0..=87.0).contains(&t) {
+                hi += ((rh - 85.0) / 10.0) * ((87.0 - t) / 5.0);
+            }
+
+            hi
+        };
+
+        round_to_milli((hi - 32.0) / 1.8)
+    }
+
+    /// Return the vapor pressure deficit in pascal.
+    ///
+    /// The saturation vapor pressure is calculated with the Tetens equation.
+    /// Returns 0 when the relative humidity is at or above 100 %.
+    #[cfg(feature = "libm")]
+    pub fn vapor_pressure_deficit_pa(&self) -> u32 {
+        let t = self.temperature.as_degrees_celsius();
+        let rh = self.humidity.clamped().as_percent() / 100.0;
+
+        let saturation = 610.78 * libm::expf(17.27 * t / (t + 237.3));
+
+        libm::roundf(saturation * (1.0 - rh)) as u32
+    }
+
+    /// Return the measured temperature as a `uom` thermodynamic temperature.
+    #[cfg(feature = "uom")]
+    pub fn as_thermodynamic_temperature(&self) -> uom::si::f32::ThermodynamicTemperature {
+        self.temperature.as_thermodynamic_temperature()
+    }
+
+    /// Return the measured relative humidity as a `uom` ratio.
+    #[cfg(feature = "uom")]
+    pub fn as_humidity_ratio(&self) -> uom::si::f32::Ratio {
+        self.humidity.as_ratio()
+    }
+
+    /// Return the difference between this measurement and `other`.
+    pub const fn delta(&self, other: &Self) -> MeasurementDelta {
+        MeasurementDelta {
+            temperature: TemperatureDelta(self.temperature.0.saturating_sub(other.temperature.0)),
+            humidity: HumidityDelta(self.humidity.0.saturating_sub(other.humidity.0)),
+        }
+    }
+}
+
 impl core::ops::AddAssign for Measurement {
     fn add_assign(&mut self, rhs: Self) {
         self.temperature.0 += rhs.temperature.0;
@@ -32,12 +153,283 @@ impl core::ops::DivAssign<i32> for Measurement {
     }
 }
 
+/// A combined temperature / humidity measurement where either half may be
+/// missing, for example because it failed CRC validation.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PartialMeasurement {
+    /// The measured temperature, if valid.
+    pub temperature: Option<Temperature>,
+    /// The measured humidity, if valid.
+    pub humidity: Option<Humidity>,
+}
+
+/// A difference between two temperature measurements.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TemperatureDelta(i32);
+
+/// A difference between two humidity measurements.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HumidityDelta(i32);
+
+/// A difference between two combined temperature / humidity measurements.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MeasurementDelta {
+    /// The temperature difference.
+    pub temperature: TemperatureDelta,
+    /// The humidity difference.
+    pub humidity: HumidityDelta,
+}
+
+impl core::ops::Sub for Temperature {
+    type Output = TemperatureDelta;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        TemperatureDelta(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl core::ops::Sub for Humidity {
+    type Output = HumidityDelta;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        HumidityDelta(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl TemperatureDelta {
+    /// Create a new `TemperatureDelta` from milli-degrees.
+    pub const fn from_millidegrees(millidegrees: i32) -> Self {
+        Self(millidegrees)
+    }
+
+    /// Return the absolute temperature difference.
+    pub const fn abs(self) -> Self {
+        Self(self.0.saturating_abs())
+    }
+
+    /// Return the temperature difference in milli-degrees.
+    pub const fn as_millidegrees(&self) -> i32 {
+        self.0
+    }
+}
+
+impl HumidityDelta {
+    /// Create a new `HumidityDelta` from 1/1000 %RH.
+    pub const fn from_millipercent(millipercent: i32) -> Self {
+        Self(millipercent)
+    }
+
+    /// Return the absolute humidity difference.
+    pub const fn abs(self) -> Self {
+        Self(self.0.saturating_abs())
+    }
+
+    /// Return the humidity difference in 1/1000 %RH.
+    pub const fn as_millipercent(&self) -> i32 {
+        self.0
+    }
+}
+
+/// An overflow-safe accumulator for averaging measurements.
+///
+/// Temperature and humidity are summed into `i64`s, so many samples can be
+/// accumulated without overflowing the `i32` fields of [`Measurement`].
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MeasurementAccumulator {
+    temperature: i64,
+    humidity: i64,
+    count: u32,
+    min: Measurement,
+    max: Measurement,
+}
+
+impl MeasurementAccumulator {
+    /// Create a new, empty accumulator.
+    pub const fn new() -> Self {
+        Self {
+            temperature: 0,
+            humidity: 0,
+            count: 0,
+            min: Measurement {
+                temperature: Temperature(0),
+                humidity: Humidity(0),
+            },
+            max: Measurement {
+                temperature: Temperature(0),
+                humidity: Humidity(0),
+            },
+        }
+    }
+
+    /// Add a measurement to the accumulator.
+    #[doc(alias = "push")]
+    pub fn add(&mut self, measurement: Measurement) {
+        if self.count == 0 {
+            self.min = measurement;
+            self.max = measurement;
+        } else {
+            self.min = self.min.min(measurement);
+            self.max = self.max.max(measurement);
+        }
+
+        self.temperature += i64::from(measurement.temperature.0);
+        self.humidity += i64::from(measurement.humidity.0);
+        self.count += 1;
+    }
+
+    /// Return the number of accumulated measurements.
+    pub const fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Return the mean of all accumulated measurements, rounded to the
+    /// nearest value. Returns `None` if no measurements were added.
+    #[doc(alias = "average")]
+    pub const fn mean(&self) -> Option<Measurement> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let count = self.count as i64;
+
+        Some(Measurement {
+            temperature: Temperature(div_round(self.temperature, count) as i32),
+            humidity: Humidity(div_round(self.humidity, count) as i32),
+        })
+    }
+
+    /// Return the component-wise minimum of all accumulated measurements.
+    /// Returns `None` if no measurements were added.
+    pub const fn min(&self) -> Option<Measurement> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.min)
+        }
+    }
+
+    /// Return the component-wise maximum of all accumulated measurements.
+    /// Returns `None` if no measurements were added.
+    pub const fn max(&self) -> Option<Measurement> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.max)
+        }
+    }
+}
+
+impl core::iter::Sum<Measurement> for MeasurementAccumulator {
+    fn sum<I: Iterator<Item = Measurement>>(iter: I) -> Self {
+        let mut acc = Self::new();
+        for measurement in iter {
+            acc.add(measurement);
+        }
+        acc
+    }
+}
+
+impl<'a> core::iter::Sum<&'a Measurement> for MeasurementAccumulator {
+    fn sum<I: Iterator<Item = &'a Measurement>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// Return the mean of the measurements, rounded to the nearest value.
+/// Returns `None` if the iterator is empty.
+///
+/// The measurements are summed in a [`MeasurementAccumulator`], so this
+/// doesn't overflow.
+pub fn average(measurements: impl IntoIterator<Item = Measurement>) -> Option<Measurement> {
+    measurements
+        .into_iter()
+        .sum::<MeasurementAccumulator>()
+        .mean()
+}
+
+/// Running statistics over an unbounded number of measurements, e.g. the
+/// daily minimum and maximum of a weather station.
+///
+/// Once `u32::MAX` samples were counted, the counter saturates. Further
+/// samples still update the minimum and maximum, but no longer the mean.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MeasurementStats {
+    acc: MeasurementAccumulator,
+}
+
+impl MeasurementStats {
+    /// Create new, empty statistics.
+    pub const fn new() -> Self {
+        Self {
+            acc: MeasurementAccumulator::new(),
+        }
+    }
+
+    /// Update the statistics with a measurement.
+    pub fn update(&mut self, measurement: Measurement) {
+        if self.acc.count == u32::MAX {
+            self.acc.min = self.acc.min.min(measurement);
+            self.acc.max = self.acc.max.max(measurement);
+        } else {
+            self.acc.add(measurement);
+        }
+    }
+
+    /// Return the number of measurements, saturating at `u32::MAX`.
+    pub const fn count(&self) -> u32 {
+        self.acc.count()
+    }
+
+    /// Return the component-wise minimum. Returns `None` if no measurements
+    /// were added.
+    pub const fn min(&self) -> Option<Measurement> {
+        self.acc.min()
+    }
+
+    /// Return the component-wise maximum. Returns `None` if no measurements
+    /// were added.
+    pub const fn max(&self) -> Option<Measurement> {
+        self.acc.max()
+    }
+
+    /// Return the mean, rounded to the nearest value. Returns `None` if no
+    /// measurements were added.
+    pub const fn mean(&self) -> Option<Measurement> {
+        self.acc.mean()
+    }
+
+    /// Clear the statistics, e.g. at the start of a new day.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl FromIterator<Measurement> for MeasurementStats {
+    fn from_iter<I: IntoIterator<Item = Measurement>>(iter: I) -> Self {
+        let mut stats = Self::new();
+        for measurement in iter {
+            stats.update(measurement);
+        }
+        stats
+    }
+}
+
 /// A combined raw temperature / humidity measurement.
 ///
 /// The raw values are of type u16. They require a conversion formula for
 /// conversion to a temperature / humidity value (see datasheet).
+///
+/// With the `serde` feature, this is serialized as a struct with the fields
+/// `temperature` and `humidity`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RawMeasurement {
     /// The measured temperature (raw value).
     pub temperature: u16,
@@ -45,6 +437,25 @@ pub struct RawMeasurement {
     pub humidity: u16,
 }
 
+impl RawMeasurement {
+    /// Return the measurement as the 6 bytes sent by the sensor in
+    /// temperature first order, including the CRC8 checksums.
+    pub const fn to_bytes_with_crc(&self) -> [u8; 6] {
+        let [t0, t1] = self.temperature.to_be_bytes();
+        let [h0, h1] = self.humidity.to_be_bytes();
+        [t0, t1, crc8(&[t0, t1]), h0, h1, crc8(&[h0, h1])]
+    }
+}
+
+impl From<Measurement> for RawMeasurement {
+    fn from(other: Measurement) -> Self {
+        Self {
+            temperature: other.temperature.to_raw(),
+            humidity: other.humidity.to_raw(),
+        }
+    }
+}
+
 impl From<RawMeasurement> for Measurement {
     fn from(other: RawMeasurement) -> Self {
         Self {
@@ -54,10 +465,47 @@ impl From<RawMeasurement> for Measurement {
     }
 }
 
+impl From<Measurement> for (f32, f32) {
+    fn from(other: Measurement) -> Self {
+        other.as_tuple()
+    }
+}
+
 impl Temperature {
+    /// The freezing point of water, 0 °C.
+    pub const FREEZING: Self = Self(0);
+    /// The boiling point of water at sea level, 100 °C.
+    pub const BOILING: Self = Self(100_000);
+    /// The lowest temperature a raw measurement can hold, from `0x0000`.
+    pub const MIN: Self = Self::from_raw(0x0000);
+    /// The highest temperature a raw measurement can hold, from `0xFFFF`.
+    pub const MAX: Self = Self::from_raw(0xFFFF);
+
+    /// Lower end of the specified operating range, -40 °C.
+    const OPERATING_MIN: i32 = -40_000;
+    /// Upper end of the specified operating range, 125 °C.
+    const OPERATING_MAX: i32 = 125_000;
+
     /// Create a new `Temperature` from a raw measurement result.
     pub const fn from_raw(raw: u16) -> Self {
-        Self(convert_temperature(raw))
+        Self(temperature_from_raw(raw))
+    }
+
+    /// Return the nearest raw measurement value, saturated to the sensor's
+    /// range of -45 to 130 °C.
+    pub const fn to_raw(&self) -> u16 {
+        raw_from_millidegrees(self.0)
+    }
+
+    /// Create a new `Temperature` from milli-degrees celsius.
+    pub const fn from_millidegrees_celsius(millidegrees: i32) -> Self {
+        Self(millidegrees)
+    }
+
+    /// Create a new `Temperature` from degrees celsius, rounded to the
+    /// nearest milli-degree.
+    pub const fn from_degrees_celsius(degrees: f32) -> Self {
+        Self(round_to_milli(degrees))
     }
 
     /// Return temperature in milli-degrees celsius.
@@ -65,26 +513,95 @@ impl Temperature {
         self.0
     }
 
-    /// Return temperature in degrees celcius with 0.01 precision
+    /// Return temperature in degrees celcius with 0.01 precision, rounded
+    /// half away from zero.
     pub const fn as_10mk_celsius(&self) -> i16 {
-        (self.0 / 10) as i16
+        div_round(self.0 as i64, 10) as i16
     }
 
     /// Return temperature in degrees celsius.
     pub const fn as_degrees_celsius(&self) -> f32 {
         self.0 as f32 / 1000.0
     }
+
+    /// Return temperature in milli-kelvin, saturating at `i32::MAX`.
+    pub const fn as_millikelvin(&self) -> i32 {
+        self.0.saturating_add(273_150)
+    }
+
+    /// Return temperature in kelvin.
+    pub const fn as_kelvin(&self) -> f32 {
+        self.as_millikelvin() as f32 / 1000.0
+    }
+
+    /// Check that the temperature is within the sensor's specified operating
+    /// range of -40 to 125 °C.
+    ///
+    /// Readings outside of it, like the -45 °C of an all-zero frame, point
+    /// to a stuck bus or a bad offset rather than a real temperature.
+    pub const fn is_plausible(&self) -> bool {
+        self.0 >= Self::OPERATING_MIN && self.0 <= Self::OPERATING_MAX
+    }
+
+    /// Return the higher of two temperatures, usable in `const` contexts.
+    pub const fn max(self, other: Self) -> Self {
+        if self.0 >= other.0 { self } else { other }
+    }
+
+    /// Return the lower of two temperatures, usable in `const` contexts.
+    pub const fn min(self, other: Self) -> Self {
+        if self.0 <= other.0 { self } else { other }
+    }
+
+    /// Return temperature as a `uom` thermodynamic temperature.
+    #[cfg(feature = "uom")]
+    pub fn as_thermodynamic_temperature(&self) -> uom::si::f32::ThermodynamicTemperature {
+        uom::si::f32::ThermodynamicTemperature::new::<
+            uom::si::thermodynamic_temperature::degree_celsius,
+        >(self.as_degrees_celsius())
+    }
 }
 
 impl Humidity {
+    /// Completely dry air, 0 %RH.
+    pub const DRY: Self = Self(0);
+    /// Saturated air, 100 %RH.
+    pub const SATURATED: Self = Self(100_000);
+    /// The lowest humidity a raw measurement can hold, from `0x0000`.
+    pub const MIN: Self = Self::from_raw(0x0000);
+    /// The highest humidity a raw measurement can hold, from `0xFFFF`.
+    pub const MAX: Self = Self::from_raw(0xFFFF);
+
     /// Create a new `Humidity` from a raw measurement result.
+    ///
+    /// The conversion always yields a value within 0-100 %RH, so no
+    /// clamping is needed. Values computed from other sources (such as
+    /// offsets or averages) can be limited with [`Humidity::clamped`].
     pub const fn from_raw(raw: u16) -> Self {
-        Self(convert_humidity(raw))
+        Self(humidity_from_raw(raw))
     }
 
-    /// Return relative humidity in 1/100 %RH
+    /// Return the nearest raw measurement value, saturated to the sensor's
+    /// range of 0 to 100 %RH.
+    pub const fn to_raw(&self) -> u16 {
+        raw_from_millipercent(self.0)
+    }
+
+    /// Create a new `Humidity` from 1/1000 %RH, clamped to 0-100 %RH.
+    pub const fn from_millipercent(millipercent: i32) -> Self {
+        Self(clamp_millipercent(millipercent))
+    }
+
+    /// Create a new `Humidity` from %RH, rounded to the nearest 1/1000 %RH
+    /// and clamped to 0-100 %RH.
+    pub const fn from_percent(percent: f32) -> Self {
+        Self(clamp_millipercent(round_to_milli(percent)))
+    }
+
+    /// Return relative humidity in 1/100 %RH, rounded half away from zero and
+    /// saturated to 0-100 %RH.
     pub const fn as_10mk_percent(&self) -> u16 {
-        (self.0 / 10).unsigned_abs() as u16
+        div_round(clamp_millipercent(self.0) as i64, 10) as u16
     }
 
     /// Return relative humidity in 1/1000 %RH.
@@ -92,87 +609,730 @@ impl Humidity {
         self.0
     }
 
-    /// Return relative humidity in 1 %RH
+    /// Return relative humidity in 1 %RH, saturated to 0-100 %RH.
     pub const fn as_1k_percent(&self) -> u8 {
-        (self.0 / 1000).unsigned_abs() as u8
+        (clamp_millipercent(self.0) / 1000) as u8
+    }
+
+    /// Return the humidity clamped to the physical 0-100 %RH range.
+    pub const fn clamped(self) -> Self {
+        Self(clamp_millipercent(self.0))
     }
 
     /// Return relative humidity in %RH.
     pub const fn as_percent(&self) -> f32 {
         self.0 as f32 / 1000.0
     }
+
+    /// Check that the humidity is within 0-100 %RH.
+    ///
+    /// Raw measurements always are, but values with an offset applied may
+    /// not be.
+    pub const fn is_plausible(&self) -> bool {
+        self.0 >= Self::DRY.0 && self.0 <= Self::SATURATED.0
+    }
+
+    /// Return the higher of two humidities, usable in `const` contexts.
+    pub const fn max(self, other: Self) -> Self {
+        if self.0 >= other.0 { self } else { other }
+    }
+
+    /// Return the lower of two humidities, usable in `const` contexts.
+    pub const fn min(self, other: Self) -> Self {
+        if self.0 <= other.0 { self } else { other }
+    }
+
+    /// Return relative humidity as a `uom` ratio.
+    #[cfg(feature = "uom")]
+    pub fn as_ratio(&self) -> uom::si::f32::Ratio {
+        uom::si::f32::Ratio::new::<uom::si::ratio::percent>(self.as_percent())
+    }
+}
+
+impl core::fmt::Display for Temperature {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_hundredths(f, self.0, "°C")
+    }
+}
+
+impl core::fmt::Display for Humidity {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt_hundredths(f, self.0, "%RH")
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Temperature {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        let abs = self.0.unsigned_abs();
+        let sign = if self.0 < 0 { "-" } else { "" };
+        defmt::write!(f, "{=str}{=u32}.{=u32:03}°C", sign, abs / 1000, abs % 1000);
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Humidity {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        let abs = self.0.unsigned_abs();
+        let sign = if self.0 < 0 { "-" } else { "" };
+        defmt::write!(f, "{=str}{=u32}.{=u32:03}%RH", sign, abs / 1000, abs % 1000);
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Measurement {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "{}, {}", self.temperature, self.humidity);
+    }
+}
+
+/// Write a milli-unit value with two decimals of precision, followed by the unit.
+fn fmt_hundredths(f: &mut core::fmt::Formatter<'_>, milli: i32, unit: &str) -> core::fmt::Result {
+    let hundredths = div_round(milli as i64, 10);
+    let sign = if hundredths < 0 { "-" } else { "" };
+    let abs = hundredths.unsigned_abs();
+    write!(f, "{}{}.{:02} {}", sign, abs / 100, abs % 100, unit)
 }
 
 /// Convert raw temperature measurement to milli-degrees celsius.
-///
-/// Formula (datasheet 5.11): -45 + 175 * (val / 2^16),
-/// optimized for fixed point math.
+#[deprecated(note = "use `conversion::temperature_from_raw` instead")]
 #[inline]
-const fn convert_temperature(temp_raw: u16) -> i32 {
-    (((temp_raw as u32) * 21875) >> 13) as i32 - 45000
+pub const fn raw_to_millidegrees_celsius(temp_raw: u16) -> i32 {
+    temperature_from_raw(temp_raw)
 }
 
-/// Convert raw humidity measurement to relative humidity.
-///
-/// Formula (datasheet 5.11): 100 * (val / 2^16),
-/// optimized for fixed point math.
+/// Convert raw humidity measurement to relative humidity in 1/1000 %RH.
+#[deprecated(note = "use `conversion::humidity_from_raw` instead")]
+#[inline]
+pub const fn raw_to_millipercent(humi_raw: u16) -> i32 {
+    humidity_from_raw(humi_raw)
+}
+
+/// Divide with rounding half away from zero.
+#[inline]
+const fn div_round(value: i64, divisor: i64) -> i64 {
+    if value < 0 {
+        (value - divisor / 2) / divisor
+    } else {
+        (value + divisor / 2) / divisor
+    }
+}
+
+/// Scale a value by 1000 and round half away from zero.
+#[inline]
+const fn round_to_milli(value: f32) -> i32 {
+    let scaled = value * 1000.0;
+    if scaled < 0.0 {
+        (scaled - 0.5) as i32
+    } else {
+        (scaled + 0.5) as i32
+    }
+}
+
+/// Clamp a relative humidity value to 0-100 %RH (in 1/1000 %RH).
 #[inline]
-const fn convert_humidity(humi_raw: u16) -> i32 {
-    (((humi_raw as u32) * 12500) >> 13) as i32
+const fn clamp_millipercent(millipercent: i32) -> i32 {
+    if millipercent < 0 {
+        0
+    } else if millipercent > 100_000 {
+        100_000
+    } else {
+        millipercent
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    /// Test conversion of raw measurement results into °C.
+    /// Test that the deprecated conversions still match the conversion
+    /// module.
     #[test]
-    fn test_convert_temperature() {
-        let test_data = [
-            (0x0000, -45000),
-            // Datasheet setion 5.11 "Conversion of Sensor Output"
-            ((0b0110_0100_u16 << 8) | 0b1000_1011, 23730),
-        ];
-        for td in &test_data {
-            assert_eq!(convert_temperature(td.0), td.1);
+    #[allow(deprecated)]
+    fn deprecated_conversions() {
+        for raw in [0x0000, 0x648b, 0xa133, 0xffff] {
+            assert_eq!(raw_to_millidegrees_celsius(raw), temperature_from_raw(raw));
+            assert_eq!(raw_to_millipercent(raw), humidity_from_raw(raw));
+        }
+    }
+
+    #[test]
+    fn temperature() {
+        let temp = Temperature(24123);
+        assert_eq!(temp.as_millidegrees_celsius(), 24123);
+        assert_eq!(temp.as_degrees_celsius(), 24.123);
+    }
+
+    #[test]
+    fn temperature_kelvin() {
+        let temp = Temperature(24123);
+        assert_eq!(temp.as_millikelvin(), 297_273);
+        assert_eq!(temp.as_kelvin(), 297.273);
+
+        let temp = Temperature(-40000);
+        assert_eq!(temp.as_millikelvin(), 233_150);
+        assert_eq!(temp.as_kelvin(), 233.15);
+
+        assert_eq!(Temperature(0).as_millikelvin(), 273_150);
+
+        // Extreme values, e.g. after an offset, don't overflow
+        let max = Temperature::from_millidegrees_celsius(i32::MAX);
+        assert_eq!(max.as_millikelvin(), i32::MAX);
+        let min = Temperature::from_millidegrees_celsius(i32::MIN);
+        assert_eq!(min.as_millikelvin(), i32::MIN + 273_150);
+    }
+
+    #[test]
+    fn humidity() {
+        let humi = Humidity(65432);
+        assert_eq!(humi.as_millipercent(), 65432);
+        assert_eq!(humi.as_percent(), 65.432);
+    }
+
+    #[test]
+    fn temperature_10mk_rounding() {
+        // Datasheet example value
+        assert_eq!(Temperature(23730).as_10mk_celsius(), 2373);
+        assert_eq!(Temperature(23734).as_10mk_celsius(), 2373);
+        assert_eq!(Temperature(23735).as_10mk_celsius(), 2374);
+        assert_eq!(Temperature(23999).as_10mk_celsius(), 2400);
+        assert_eq!(Temperature(-23734).as_10mk_celsius(), -2373);
+        assert_eq!(Temperature(-23735).as_10mk_celsius(), -2374);
+        assert_eq!(Temperature(-23736).as_10mk_celsius(), -2374);
+    }
+
+    #[test]
+    fn temperature_10mk_rounding_boundaries() {
+        assert_eq!(
+            Temperature::from_degrees_celsius(23.735).as_10mk_celsius(),
+            2374
+        );
+        assert_eq!(
+            Temperature::from_degrees_celsius(-23.735).as_10mk_celsius(),
+            -2374
+        );
+        assert_eq!(Temperature(5).as_10mk_celsius(), 1);
+        assert_eq!(Temperature(4).as_10mk_celsius(), 0);
+        assert_eq!(Temperature(-4).as_10mk_celsius(), 0);
+        assert_eq!(Temperature(-5).as_10mk_celsius(), -1);
+    }
+
+    #[test]
+    fn humidity_10mk_rounding() {
+        // Datasheet example value
+        assert_eq!(Humidity(62968).as_10mk_percent(), 6297);
+        assert_eq!(Humidity(62964).as_10mk_percent(), 6296);
+        assert_eq!(Humidity(62965).as_10mk_percent(), 6297);
+    }
+
+    /// Test that no raw value converts to a humidity outside of 0-100 %RH.
+    #[test]
+    fn humidity_from_raw_in_range() {
+        for raw in 0..=u16::MAX {
+            let humi = Humidity::from_raw(raw);
+            assert_eq!(humi.clamped(), humi);
         }
+        assert_eq!(Humidity::from_raw(u16::MAX).as_millipercent(), 99_998);
+        assert_eq!(Humidity::from_raw(u16::MAX).as_1k_percent(), 99);
     }
 
-    /// Test conversion of raw measurement results into %RH.
     #[test]
-    fn test_convert_humidity() {
+    fn humidity_saturation() {
         let test_data = [
-            (0x0000, 0),
-            // Datasheet setion 5.11 "Conversion of Sensor Output"
-            ((0b1010_0001_u16 << 8) | 0b0011_0011, 62968),
+            (-1, 0, 0, 0),
+            (0, 0, 0, 0),
+            (-200, 0, 0, 0),
+            (62968, 62968, 6297, 62),
+            (100_000, 100_000, 10_000, 100),
+            (100_001, 100_000, 10_000, 100),
         ];
-        for td in &test_data {
-            assert_eq!(convert_humidity(td.0), td.1);
+        for (millipercent, clamped, percent_10mk, percent_1k) in test_data {
+            let humi = Humidity(millipercent);
+            assert_eq!(humi.clamped().as_millipercent(), clamped);
+            assert_eq!(humi.as_10mk_percent(), percent_10mk);
+            assert_eq!(humi.as_1k_percent(), percent_1k);
         }
     }
 
-    /// Test conversion of raw measurement results into °C and %RH.
     #[test]
-    fn measurement_conversion() {
+    fn display() {
+        extern crate alloc;
+        use alloc::format;
+
         // Datasheet setion 5.11 "Conversion of Sensor Output"
-        let temperature = convert_temperature((0b0110_0100_u16 << 8) | 0b1000_1011);
-        let humidity = convert_humidity((0b1010_0001_u16 << 8) | 0b0011_0011);
-        assert_eq!(temperature, 23730);
-        assert_eq!(humidity, 62968);
+        assert_eq!(format!("{}", Temperature(23730)), "23.73 °C");
+        assert_eq!(format!("{}", Humidity(62968)), "62.97 %RH");
+
+        assert_eq!(format!("{}", Temperature(-45000)), "-45.00 °C");
+        assert_eq!(format!("{}", Temperature(-50)), "-0.05 °C");
+        assert_eq!(format!("{}", Temperature(4)), "0.00 °C");
+        assert_eq!(format!("{}", Humidity(100_000)), "100.00 %RH");
     }
 
     #[test]
-    fn temperature() {
-        let temp = Temperature(24123);
+    fn temperature_from_units() {
+        let temp = Temperature::from_millidegrees_celsius(24123);
+        assert_eq!(temp, Temperature(24123));
+        assert_eq!(temp.as_millidegrees_celsius(), 24123);
+
+        let temp = Temperature::from_degrees_celsius(24.123);
         assert_eq!(temp.as_millidegrees_celsius(), 24123);
         assert_eq!(temp.as_degrees_celsius(), 24.123);
+
+        let temp = Temperature::from_degrees_celsius(-12.345);
+        assert_eq!(temp.as_millidegrees_celsius(), -12345);
+        assert_eq!(temp.as_degrees_celsius(), -12.345);
     }
 
     #[test]
-    fn humidity() {
-        let humi = Humidity(65432);
+    fn humidity_from_units() {
+        let humi = Humidity::from_millipercent(65432);
+        assert_eq!(humi, Humidity(65432));
+        assert_eq!(humi.as_millipercent(), 65432);
+
+        let humi = Humidity::from_percent(65.432);
         assert_eq!(humi.as_millipercent(), 65432);
         assert_eq!(humi.as_percent(), 65.432);
+
+        // Out of range values are clamped
+        assert_eq!(Humidity::from_millipercent(-1).as_millipercent(), 0);
+        assert_eq!(
+            Humidity::from_millipercent(100_001).as_millipercent(),
+            100_000
+        );
+        assert_eq!(Humidity::from_percent(-5.0).as_millipercent(), 0);
+        assert_eq!(Humidity::from_percent(105.0).as_millipercent(), 100_000);
+    }
+
+    #[test]
+    fn ordering() {
+        let cold = Temperature::from_millidegrees_celsius(-5000);
+        let freezing = Temperature::from_millidegrees_celsius(0);
+        let warm = Temperature::from_millidegrees_celsius(21000);
+        assert!(cold < freezing);
+        assert!(freezing < warm);
+        assert_eq!(cold.max(warm), warm);
+        assert_eq!(cold.min(warm), cold);
+
+        let dry = Humidity::from_millipercent(20000);
+        let wet = Humidity::from_millipercent(80000);
+        assert!(dry < wet);
+    }
+
+    #[test]
+    fn ordering_constants() {
+        const FROST_LIMIT: Temperature = Temperature::FREEZING.max(Temperature(-1000));
+        assert_eq!(FROST_LIMIT, Temperature::FREEZING);
+
+        let below = Temperature::from_millidegrees_celsius(-1);
+        let above = Temperature::from_millidegrees_celsius(1);
+        assert!(below < Temperature::FREEZING);
+        assert!(above > Temperature::FREEZING);
+        assert!(above < Temperature::BOILING);
+        assert_eq!(below.cmp(&above), core::cmp::Ordering::Less);
+        assert_eq!(below.min(above), below);
+        assert_eq!(below.max(above), above);
+        assert_eq!(
+            Temperature::FREEZING.cmp(&Temperature::from_millidegrees_celsius(0)),
+            core::cmp::Ordering::Equal
+        );
+
+        let humid = Humidity::from_millipercent(99_999);
+        assert!(humid < Humidity::SATURATED);
+        assert!(humid > Humidity::DRY);
+        assert_eq!(Humidity::from_percent(150.0), Humidity::SATURATED);
+        assert_eq!(Humidity::from_millipercent(-5), Humidity::DRY);
+        assert_eq!(humid.max(Humidity::SATURATED), Humidity::SATURATED);
+        assert_eq!(humid.min(Humidity::DRY), Humidity::DRY);
+    }
+
+    #[test]
+    fn plausibility() {
+        assert_eq!(Temperature::MIN.as_millidegrees_celsius(), -45_000);
+        assert_eq!(Temperature::MAX.as_millidegrees_celsius(), 129_997);
+        assert_eq!(Humidity::MIN.as_millipercent(), 0);
+        assert_eq!(Humidity::MAX.as_millipercent(), 99_998);
+
+        assert!(!Temperature::MIN.is_plausible());
+        assert!(!Temperature::MAX.is_plausible());
+        assert!(Temperature::from_millidegrees_celsius(-40_000).is_plausible());
+        assert!(Temperature::from_millidegrees_celsius(125_000).is_plausible());
+        assert!(!Temperature::from_millidegrees_celsius(125_001).is_plausible());
+
+        assert!(Humidity::MIN.is_plausible());
+        assert!(Humidity::MAX.is_plausible());
+        assert!(!Humidity(100_001).is_plausible());
+        assert!(!Humidity(-1).is_plausible());
+
+        // All-zero and all-ones frames
+        let zeros = Measurement {
+            temperature: Temperature::from_raw(0x0000),
+            humidity: Humidity::from_raw(0x0000),
+        };
+        let ones = Measurement {
+            temperature: Temperature::from_raw(0xFFFF),
+            humidity: Humidity::from_raw(0xFFFF),
+        };
+        assert!(!zeros.is_plausible());
+        assert!(!ones.is_plausible());
+
+        let typical = Measurement {
+            temperature: Temperature::from_millidegrees_celsius(23_730),
+            humidity: Humidity::from_millipercent(45_000),
+        };
+        assert!(typical.is_plausible());
+    }
+
+    #[test]
+    fn measurement_min_max() {
+        let a = Measurement {
+            temperature: Temperature::from_millidegrees_celsius(-12000),
+            humidity: Humidity::from_millipercent(70000),
+        };
+        let b = Measurement {
+            temperature: Temperature::from_millidegrees_celsius(-3000),
+            humidity: Humidity::from_millipercent(40000),
+        };
+
+        let min = a.min(b);
+        assert_eq!(min.temperature.as_millidegrees_celsius(), -12000);
+        assert_eq!(min.humidity.as_millipercent(), 40000);
+
+        let max = a.max(b);
+        assert_eq!(max.temperature.as_millidegrees_celsius(), -3000);
+        assert_eq!(max.humidity.as_millipercent(), 70000);
+    }
+
+    #[cfg(feature = "libm")]
+    #[test]
+    fn heat_index() {
+        /// Convert from degrees fahrenheit to milli-degrees celsius
+        fn fahrenheit(f: f32) -> i32 {
+            ((f - 32.0) / 1.8 * 1000.0) as i32
+        }
+
+        // Reference points from the NOAA heat index table (°F, %RH, °F)
+        let test_data = [
+            (80.0, 40.0, 80.0),
+            (90.0, 70.0, 106.0),
+            (96.0, 50.0, 108.0),
+            (100.0, 40.0, 109.0),
+            (86.0, 90.0, 105.0),
+        ];
+
+        for (t, rh, expected) in test_data {
+            let measurement = Measurement {
+                temperature: Temperature(fahrenheit(t)),
+                humidity: Humidity::from_percent(rh),
+            };
+            let hi = measurement.heat_index_millidegrees_celsius();
+            // The NOAA table is rounded to whole degrees fahrenheit
+            assert!(
+                (hi - fahrenheit(expected)).abs() <= 600,
+                "{t}°F / {rh}% -> {hi}"
+            );
+        }
+
+        // Below the regression range the simple formula is used
+        let measurement = Measurement {
+            temperature: Temperature(20000),
+            humidity: Humidity(50000),
+        };
+        assert_eq!(measurement.heat_index_millidegrees_celsius(), 19361);
+    }
+
+    #[cfg(feature = "libm")]
+    #[test]
+    fn vapor_pressure_deficit() {
+        // (m°C, m%RH, Pa)
+        let test_data = [
+            (25000, 50000, 1584),
+            (20000, 60000, 935),
+            (30000, 80000, 849),
+        ];
+
+        for (t, rh, expected) in test_data {
+            let measurement = Measurement {
+                temperature: Temperature(t),
+                humidity: Humidity(rh),
+            };
+            let vpd = measurement.vapor_pressure_deficit_pa();
+            assert!(vpd.abs_diff(expected) <= 5, "{t} / {rh} -> {vpd}");
+        }
+
+        // Saturated or supersaturated air has no deficit
+        for rh in [100000, 104000] {
+            let measurement = Measurement {
+                temperature: Temperature(25000),
+                humidity: Humidity(rh),
+            };
+            assert_eq!(measurement.vapor_pressure_deficit_pa(), 0);
+        }
+    }
+
+    #[test]
+    fn deltas() {
+        const TEMP_THRESHOLD: TemperatureDelta = TemperatureDelta::from_millidegrees(200);
+        const HUMI_THRESHOLD: HumidityDelta = HumidityDelta::from_millipercent(1000);
+
+        let previous = Measurement {
+            temperature: Temperature::from_millidegrees_celsius(21000),
+            humidity: Humidity::from_millipercent(50000),
+        };
+        let current = Measurement {
+            temperature: Temperature::from_millidegrees_celsius(20750),
+            humidity: Humidity::from_millipercent(50500),
+        };
+
+        let delta = current.delta(&previous);
+        assert_eq!(delta.temperature.as_millidegrees(), -250);
+        assert_eq!(delta.humidity.as_millipercent(), 500);
+        assert!(delta.temperature < TEMP_THRESHOLD);
+        assert!(delta.temperature.abs() > TEMP_THRESHOLD);
+        assert!(delta.humidity.abs() < HUMI_THRESHOLD);
+
+        assert_eq!(
+            current.temperature - previous.temperature,
+            delta.temperature
+        );
+        assert_eq!(current.humidity - previous.humidity, delta.humidity);
+    }
+
+    #[test]
+    fn deltas_saturate() {
+        let delta = Temperature(i32::MIN) - Temperature(1);
+        assert_eq!(delta.as_millidegrees(), i32::MIN);
+        assert_eq!(delta.abs().as_millidegrees(), i32::MAX);
+
+        let delta = Temperature(i32::MAX) - Temperature(-1);
+        assert_eq!(delta.as_millidegrees(), i32::MAX);
+
+        let delta = Humidity(i32::MIN) - Humidity(i32::MAX);
+        assert_eq!(delta.as_millipercent(), i32::MIN);
+        assert_eq!(delta.abs().as_millipercent(), i32::MAX);
+    }
+
+    #[test]
+    fn stats() {
+        let mut stats = MeasurementStats::new();
+        assert_eq!(stats.count(), 0);
+        assert_eq!(stats.mean(), None);
+        assert_eq!(stats.min(), None);
+
+        let first = Measurement {
+            temperature: Temperature(-2000),
+            humidity: Humidity(80000),
+        };
+        stats.update(first);
+        assert_eq!(stats.count(), 1);
+        assert_eq!(stats.min(), Some(first));
+        assert_eq!(stats.max(), Some(first));
+        assert_eq!(stats.mean(), Some(first));
+
+        for (temperature, humidity) in [(3000, 60000), (8000, 40000), (1000, 70000)] {
+            stats.update(Measurement {
+                temperature: Temperature(temperature),
+                humidity: Humidity(humidity),
+            });
+        }
+        assert_eq!(stats.count(), 4);
+        assert_eq!(
+            stats.min(),
+            Some(Measurement {
+                temperature: Temperature(-2000),
+                humidity: Humidity(40000),
+            })
+        );
+        assert_eq!(
+            stats.max(),
+            Some(Measurement {
+                temperature: Temperature(8000),
+                humidity: Humidity(80000),
+            })
+        );
+        assert_eq!(
+            stats.mean(),
+            Some(Measurement {
+                temperature: Temperature(2500),
+                humidity: Humidity(62500),
+            })
+        );
+
+        stats.reset();
+        assert_eq!(stats, MeasurementStats::default());
+        assert_eq!(stats.count(), 0);
+        assert_eq!(stats.max(), None);
+
+        let second = Measurement {
+            temperature: Temperature(20000),
+            humidity: Humidity(50000),
+        };
+        stats.update(second);
+        assert_eq!(stats.min(), Some(second));
+        assert_eq!(stats.max(), Some(second));
+    }
+
+    #[test]
+    fn sum_and_average() {
+        let readings = [
+            Measurement {
+                temperature: Temperature(20000),
+                humidity: Humidity(40000),
+            },
+            Measurement {
+                temperature: Temperature(23000),
+                humidity: Humidity(50000),
+            },
+        ];
+
+        let acc: MeasurementAccumulator = readings.iter().sum();
+        assert_eq!(acc.count(), 2);
+        let acc: MeasurementAccumulator = readings.iter().copied().sum();
+        assert_eq!(
+            acc.mean(),
+            Some(Measurement {
+                temperature: Temperature(21500),
+                humidity: Humidity(45000),
+            })
+        );
+        assert_eq!(average(readings), acc.mean());
+        assert_eq!(average([]), None);
+
+        let stats: MeasurementStats = readings.into_iter().collect();
+        assert_eq!(stats.count(), 2);
+        assert_eq!(stats.min(), Some(readings[0]));
+        assert_eq!(stats.max(), Some(readings[1]));
+    }
+
+    /// Test that averaging many extreme readings doesn't overflow.
+    #[test]
+    fn average_worst_case() {
+        let max = Measurement {
+            temperature: Temperature(i32::MAX),
+            humidity: Humidity(i32::MAX),
+        };
+        assert_eq!(average(core::iter::repeat_n(max, 10_000)), Some(max));
+
+        let min = Measurement {
+            temperature: Temperature(i32::MIN),
+            humidity: Humidity(i32::MIN),
+        };
+        assert_eq!(average(core::iter::repeat_n(min, 10_000)), Some(min));
+    }
+
+    #[test]
+    fn stats_count_saturates() {
+        let sample = Measurement {
+            temperature: Temperature(1000),
+            humidity: Humidity(50000),
+        };
+        let mut stats = MeasurementStats::new();
+        stats.update(sample);
+        stats.acc.count = u32::MAX;
+
+        stats.update(Measurement {
+            temperature: Temperature(5000),
+            humidity: Humidity(10000),
+        });
+        assert_eq!(stats.count(), u32::MAX);
+        assert_eq!(stats.max().unwrap().temperature, Temperature(5000));
+        assert_eq!(stats.min().unwrap().humidity, Humidity(10000));
+    }
+
+    #[test]
+    fn accumulator_empty() {
+        let acc = MeasurementAccumulator::new();
+        assert_eq!(acc.count(), 0);
+        assert_eq!(acc.mean(), None);
+        assert_eq!(acc.min(), None);
+        assert_eq!(acc.max(), None);
+    }
+
+    #[test]
+    fn accumulator_mean_rounds() {
+        let mut acc = MeasurementAccumulator::default();
+        acc.add(Measurement {
+            temperature: Temperature(-1000),
+            humidity: Humidity(50000),
+        });
+        acc.add(Measurement {
+            temperature: Temperature(-1003),
+            humidity: Humidity(50003),
+        });
+
+        let mean = acc.mean().unwrap();
+        assert_eq!(mean.temperature.as_millidegrees_celsius(), -1002);
+        assert_eq!(mean.humidity.as_millipercent(), 50002);
+
+        assert_eq!(acc.min().unwrap().temperature.0, -1003);
+        assert_eq!(acc.min().unwrap().humidity.0, 50000);
+        assert_eq!(acc.max().unwrap().temperature.0, -1000);
+        assert_eq!(acc.max().unwrap().humidity.0, 50003);
+    }
+
+    #[test]
+    fn accumulator_no_overflow() {
+        let max = Measurement {
+            temperature: Temperature(i32::MAX),
+            humidity: Humidity(i32::MAX),
+        };
+
+        let mut acc = MeasurementAccumulator::default();
+        for _ in 0..1000 {
+            acc.add(max);
+        }
+
+        assert_eq!(acc.count(), 1000);
+        assert_eq!(acc.mean(), Some(max));
+        assert_eq!(acc.min(), Some(max));
+        assert_eq!(acc.max(), Some(max));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let measurement = Measurement {
+            temperature: Temperature(-12345),
+            humidity: Humidity(62968),
+        };
+        let mut buf = [0u8; 16];
+        let bytes = postcard::to_slice(&measurement, &mut buf).unwrap();
+        let decoded: Measurement = postcard::from_bytes(bytes).unwrap();
+        assert_eq!(decoded, measurement);
+
+        let raw = RawMeasurement {
+            temperature: (0b0110_0100_u16 << 8) | 0b1000_1011,
+            humidity: (0b1010_0001_u16 << 8) | 0b0011_0011,
+        };
+        let mut buf = [0u8; 16];
+        let bytes = postcard::to_slice(&raw, &mut buf).unwrap();
+        let decoded: RawMeasurement = postcard::from_bytes(bytes).unwrap();
+        assert_eq!(decoded, raw);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_json_round_trip() {
+        let measurement = Measurement {
+            temperature: Temperature(-12345),
+            humidity: Humidity(62968),
+        };
+        let json = serde_json::to_string(&measurement).unwrap();
+        assert_eq!(
+            json,
+            r#"{"temperature_millidegrees_celsius":-12345,"humidity_millipercent":62968}"#
+        );
+        let decoded: Measurement = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, measurement);
+
+        let raw = RawMeasurement {
+            temperature: 0x648b,
+            humidity: 0xa133,
+        };
+        let json = serde_json::to_string(&raw).unwrap();
+        assert_eq!(json, r#"{"temperature":25739,"humidity":41267}"#);
+        let decoded: RawMeasurement = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, raw);
     }
 
     #[test]
@@ -196,4 +1356,76 @@ mod tests {
         // std::cmp::PartialEq
         assert_eq!(measurement1, measurement2);
     }
+
+    #[test]
+    fn measurement_to_raw() {
+        // Datasheet setion 5.11 "Conversion of Sensor Output"
+        let raw = RawMeasurement {
+            temperature: (0b0110_0100_u16 << 8) | 0b1000_1011,
+            humidity: (0b1010_0001_u16 << 8) | 0b0011_0011,
+        };
+        assert_eq!(RawMeasurement::from(Measurement::from(raw)), raw);
+        assert_eq!(
+            raw.to_bytes_with_crc(),
+            [
+                0b0110_0100,
+                0b1000_1011,
+                0b1100_0111,
+                0b1010_0001,
+                0b0011_0011,
+                0b0001_1100,
+            ]
+        );
+
+        // Engineering values survive a round trip within one raw LSB
+        for millidegrees in (-45_000..=130_000).step_by(250) {
+            let temperature = Temperature(millidegrees);
+            let round_trip = Temperature::from_raw(temperature.to_raw());
+            assert!((round_trip.0 - millidegrees).abs() <= 3, "{millidegrees}");
+        }
+        for millipercent in (0..=100_000).step_by(250) {
+            let humidity = Humidity(millipercent);
+            let round_trip = Humidity::from_raw(humidity.to_raw());
+            assert!((round_trip.0 - millipercent).abs() <= 2, "{millipercent}");
+        }
+
+        // Out of range values are clamped
+        assert_eq!(Temperature(-50_000).to_raw(), 0);
+        assert_eq!(Humidity(110_000).to_raw(), u16::MAX);
+    }
+
+    #[test]
+    fn measurement_into_tuple() {
+        // Datasheet setion 5.11 "Conversion of Sensor Output"
+        let measurement = Measurement::from(RawMeasurement {
+            temperature: (0b0110_0100_u16 << 8) | 0b1000_1011,
+            humidity: (0b1010_0001_u16 << 8) | 0b0011_0011,
+        });
+
+        assert_eq!(measurement.as_tuple(), (23.73, 62.968));
+
+        let (t, rh): (f32, f32) = measurement.into();
+        assert_eq!(t, 23.73);
+        assert_eq!(rh, 62.968);
+    }
+
+    #[cfg(feature = "uom")]
+    #[test]
+    fn uom_values() {
+        use uom::si::{ratio::percent, thermodynamic_temperature::degree_celsius};
+
+        // Datasheet setion 5.11 "Conversion of Sensor Output"
+        let measurement = Measurement::from(RawMeasurement {
+            temperature: (0b0110_0100_u16 << 8) | 0b1000_1011,
+            humidity: (0b1010_0001_u16 << 8) | 0b0011_0011,
+        });
+
+        let temperature = measurement.as_thermodynamic_temperature();
+        let celsius = measurement.temperature.as_degrees_celsius();
+        assert!((temperature.get::<degree_celsius>() - celsius).abs() < 0.001);
+
+        let humidity = measurement.as_humidity_ratio();
+        assert_eq!(humidity.get::<percent>(), measurement.humidity.as_percent());
+        assert_eq!(humidity.value, 0.62968);
+    }
 }
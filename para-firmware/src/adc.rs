@@ -6,13 +6,13 @@ use embassy_nrf::{
     saadc::{self, ChannelConfig, Config, Resolution, Saadc},
 };
 use embassy_time::Timer;
-use para_battery::BatteryDischargeProfile;
+use para_battery::VoltageFilter;
 use para_fmt::{info, unwrap};
 use static_cell::ConstStaticCell;
 
 use crate::{
     Irqs,
-    constants::{DISCARGE_PROFILES, DRY_COEFFS, WET_COEFFS},
+    constants::{BAT_FILTER_ALPHA, DISCHARGE_CURVE, DRY_COEFFS, WET_COEFFS},
     state::{ADC_MEASUREMENT, AdcMeasurements, START_MEASUREMENTS},
 };
 
@@ -96,6 +96,7 @@ pub async fn task(
     let adc_buf = ADC_BUFFER.take();
 
     let mut measure = unwrap!(START_MEASUREMENTS.receiver());
+    let mut bat_filter = VoltageFilter::new(BAT_FILTER_ALPHA);
 
     loop {
         measure.changed().await;
@@ -130,14 +131,13 @@ pub async fn task(
         let [soil, light, bat] = acc_buf;
 
         let bat_volt = to_volts(bat, VREF);
+        // Only the battery level is smoothed, soil moisture and the reported voltage stay current
+        let bat_filtered = bat_filter.update(bat_volt);
 
         let (soil, light, bat) = (
             calculate_soil_moisture(bat_volt, soil),
             calculate_lux(to_volts(light, VREF)).max(0.0),
-            BatteryDischargeProfile::calc_pct_from_profile_range(
-                bat_volt,
-                DISCARGE_PROFILES.iter(),
-            ),
+            DISCHARGE_CURVE.calc_pct_u8(bat_filtered),
         );
 
         let measurements = AdcMeasurements::new(bat, bat_volt, soil, light);
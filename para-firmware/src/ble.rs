@@ -5,7 +5,7 @@ use embassy_time::{Duration, Timer};
 use nrf_mpsl::MultiprotocolServiceLayer;
 use nrf_sdc::vendor::ZephyrWriteBdAddr;
 use para_bthome::BtHomeAd;
-use para_fmt::{info, unwrap};
+use para_fmt::{Bytes, info, unwrap};
 use trouble_host::prelude::*;
 
 use crate::{
@@ -72,19 +72,19 @@ pub async fn run(controller: nrf_sdc::SoftdeviceController<'static>) {
 
             let (adc, shtc3) = join(ADC_MEASUREMENT.wait(), SHTC3_MEASUREMENT.wait()).await;
 
-            let mut ad = BtHomeAd::default();
+            let mut ad = BtHomeAd::<31>::with_reserved_name(PARA_NAME.len());
 
             let adv_data = ad
-                .add_data(adc.battery)
-                .add_data(shtc3.temperature)
-                .add_data(adc.lux)
-                .add_data(adc.voltage)
-                .add_data(shtc3.humidity)
-                .add_data(adc.moisture)
+                .add_data_sorted(adc.battery)
+                .add_data_sorted(shtc3.temperature)
+                .add_data_sorted(adc.lux)
+                .add_data_sorted(adc.voltage)
+                .add_data_sorted(shtc3.humidity)
+                .add_data_sorted(adc.moisture)
                 .add_local_name(PARA_NAME)
                 .encode();
 
-            info!("Starting advertising");
+            info!("Starting advertising, adv = {}", Bytes(adv_data));
             let advertiser = unwrap!(
                 peripheral
                     .advertise(
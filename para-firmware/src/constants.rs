@@ -1,4 +1,4 @@
-use para_battery::BatteryDischargeProfile;
+use para_battery::BatteryCurve;
 use trouble_host::prelude::TxPower;
 
 pub const PARA_SLEEP_SECS: u64 = 300;
@@ -12,9 +12,12 @@ pub static PARA_NAME: &str = "rpara";
 pub static DRY_COEFFS: [f32; 3] = [154.0, 110.0, -15.3];
 pub static WET_COEFFS: [f32; 3] = [319.0, -63.1, 7.2];
 
-pub static DISCARGE_PROFILES: [BatteryDischargeProfile; 4] = [
-    BatteryDischargeProfile::new(3.00, 2.90, 1.00, 0.42),
-    BatteryDischargeProfile::new(2.90, 2.74, 0.42, 0.18),
-    BatteryDischargeProfile::new(2.74, 2.44, 0.18, 0.06),
-    BatteryDischargeProfile::new(2.44, 2.01, 0.06, 0.00),
-];
+pub const BAT_FILTER_ALPHA: f32 = 0.3;
+
+pub static DISCHARGE_CURVE: BatteryCurve<5> = BatteryCurve::new([
+    (2.01, 0.00),
+    (2.44, 0.06),
+    (2.74, 0.18),
+    (2.90, 0.42),
+    (3.00, 1.00),
+]);
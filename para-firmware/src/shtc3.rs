@@ -3,9 +3,11 @@ use embassy_nrf::{
     twim::{self, Twim},
 };
 use embassy_time::Timer;
-use embedded_hal::i2c::SevenBitAddress;
+use embedded_hal::i2c::ErrorKind;
 use para_fmt::{error, unwrap};
-use para_shtc3::{Error as ShtError, Measurement, PowerMode, ShtC3};
+use para_shtc3::{
+    Measurement, MeasurementAccumulator, PowerMode, ShtC3, ShtC3State, TemperatureHumiditySensor,
+};
 use static_cell::ConstStaticCell;
 
 use crate::{
@@ -13,9 +15,9 @@ use crate::{
     state::{SHTC3_MEASUREMENT, START_MEASUREMENTS, Shtc3Measurement},
 };
 
-async fn measure<I>(sht: &mut ShtC3<I>) -> Result<Measurement, ShtError<I::Error>>
+async fn measure<S>(mut sht: S) -> Result<Measurement, S::Error>
 where
-    I: embedded_hal::i2c::I2c<SevenBitAddress>,
+    S: TemperatureHumiditySensor,
 {
     sht.start_wakeup()?;
 
@@ -23,20 +25,20 @@ where
 
     let mode = PowerMode::LowPower;
 
-    let divisor = 4;
-    let mut m = Measurement::default();
+    let samples = 4;
+    let mut acc = MeasurementAccumulator::new();
 
-    for _ in 0..divisor {
+    for _ in 0..samples {
         sht.start_measurement(mode)?;
 
         Timer::after_micros(sht.max_measurement_duration(mode) as u64).await;
 
-        m += sht.get_measurement_result()?;
+        acc.add(sht.get_measurement_result()?);
 
         Timer::after_millis(5).await;
     }
 
-    m /= divisor;
+    let m = acc.mean().unwrap_or_default();
 
     info!(
         "Temp: {}C, Humi: {}%",
@@ -49,9 +51,9 @@ where
     Ok(m)
 }
 
-async fn reset<I>(sht: &mut ShtC3<I>) -> Result<(), ShtError<I::Error>>
+async fn reset<S>(mut sht: S) -> Result<(), S::Error>
 where
-    I: embedded_hal::i2c::I2c<SevenBitAddress>,
+    S: TemperatureHumiditySensor,
 {
     sht.start_reset()?;
 
@@ -65,11 +67,15 @@ fn init_sht3<'scope>(
     sda: Peri<'scope, peripherals::P0_24>,
     scl: Peri<'scope, peripherals::P0_13>,
     ram: &'scope mut [u8; 16],
+    state: Option<ShtC3State>,
 ) -> ShtC3<Twim<'scope, peripherals::TWISPI0>> {
     let config = twim::Config::default();
     let twi = Twim::new(spio, Irqs, sda, scl, config, ram);
 
-    ShtC3::new(twi)
+    match state {
+        Some(state) => ShtC3::from_parts(twi, state),
+        None => ShtC3::new(twi),
+    }
 }
 
 #[embassy_executor::task]
@@ -83,10 +89,19 @@ pub async fn task(
 
     let mut watcher = unwrap!(START_MEASUREMENTS.receiver());
 
+    // The bus is only created for each measurement, so keep the driver state between them
+    let mut state = None;
+
     loop {
         watcher.changed().await;
 
-        let mut sht = init_sht3(spio.reborrow(), sda.reborrow(), scl.reborrow(), ram);
+        let mut sht = init_sht3(
+            spio.reborrow(),
+            sda.reborrow(),
+            scl.reborrow(),
+            ram,
+            state.take(),
+        );
 
         match measure(&mut sht).await {
             Ok(measurement) => {
@@ -95,13 +110,21 @@ pub async fn task(
             Err(e) => {
                 error!("SHTC3 error: {:?}", e);
 
-                // Attempt to reset the sensor
-                if let Err(e) = reset(&mut sht).await {
-                    error!("SHTC3 reset error: {:?}", e);
+                match e.kind() {
+                    // The sensor isn't responding, so it won't receive a reset either
+                    Some(ErrorKind::NoAcknowledge(_)) => {}
+                    // Attempt to reset the sensor
+                    _ => {
+                        if let Err(e) = reset(&mut sht).await {
+                            error!("SHTC3 reset error: {:?}", e);
+                        }
+                    }
                 }
             }
         }
 
-        drop(sht);
+        let (twi, sht_state) = sht.into_parts();
+        state = Some(sht_state);
+        drop(twi);
     }
 }
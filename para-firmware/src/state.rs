@@ -13,8 +13,7 @@ pub struct AdcMeasurements {
 }
 
 impl AdcMeasurements {
-    pub fn new(battery: f32, voltage: f32, moisture: f32, lux: f32) -> Self {
-        let battery = (battery * 100.0) as u8;
+    pub fn new(battery: u8, voltage: f32, moisture: f32, lux: f32) -> Self {
         let voltage = (voltage * 1000.0) as u16;
         let moisture = (moisture * 100.0) as u8;
         let lux = (lux * 100.0) as u32;
@@ -39,8 +38,8 @@ pub struct Shtc3Measurement {
 impl Shtc3Measurement {
     pub fn new(measurement: Measurement) -> Self {
         Self {
-            temperature: measurement.temperature.as_10mk_celsius().into(),
-            humidity: measurement.humidity.as_1k_percent().into(),
+            temperature: measurement.temperature.into(),
+            humidity: measurement.humidity.into(),
         }
     }
 }